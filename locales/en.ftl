@@ -0,0 +1,485 @@
+## Commands
+
+cmd-play-name = play
+cmd-play-description = Start a game
+opt-play-thread-name = thread
+opt-play-thread-description = Create a dedicated thread for the puzzle
+choice-play-thread-none = No thread
+choice-play-thread-public = Public thread
+choice-play-thread-private = Private thread
+cmd-join-name = join
+cmd-join-description = Join the game
+cmd-question-name = question
+cmd-question-description = Ask a question
+opt-question-q-name = q
+opt-question-q-description = Enter your question
+cmd-answer-name = answer
+cmd-answer-description = Submit an answer
+opt-answer-a-name = a
+opt-answer-a-description = Enter your answer
+cmd-giveup-name = giveup
+cmd-giveup-description = Give up and end the game
+cmd-hint-name = hint
+cmd-hint-description = Get a hint (reduces the points for solving)
+cmd-pause-name = pause
+cmd-pause-description = Pause the game. The time limit stops too
+cmd-resume-name = resume
+cmd-resume-description = Resume a paused game
+cmd-chat-name = chat
+cmd-chat-description = Toggle asking and answering with plain messages
+opt-chat-enabled-name = enabled
+opt-chat-enabled-description = True to enable
+cmd-export-name = export
+cmd-export-description = Download the record of a game as a file
+opt-export-format-name = format
+opt-export-format-description = File format (both if omitted)
+choice-export-format-markdown = Markdown (for sharing)
+choice-export-format-json = JSON (for importing and analysis)
+opt-export-game-name = game
+opt-export-game-description = Game number to export (the current or latest game if omitted)
+cmd-import-name = import
+cmd-import-description = Load an exported record (JSON) and show a summary
+opt-import-file-name = file
+opt-import-file-description = A JSON file created with /export
+opt-import-play-name = play
+opt-import-play-description = Play the imported puzzle again in this channel
+cmd-solo-name = solo
+cmd-solo-description = Practise in solo mode, visible only to you
+opt-solo-start-name = start
+opt-solo-start-description = Get a solo puzzle
+opt-solo-start-difficulty-name = difficulty
+opt-solo-start-difficulty-description = The difficulty of the puzzle
+choice-solo-start-difficulty-easy = Easy
+choice-solo-start-difficulty-normal = Normal
+choice-solo-start-difficulty-hard = Hard
+opt-solo-question-name = question
+opt-solo-question-description = Ask a question
+opt-solo-question-q-name = question
+opt-solo-question-q-description = A question that can be answered with Yes or No
+opt-solo-answer-name = answer
+opt-solo-answer-description = Answer the puzzle
+opt-solo-answer-a-name = answer
+opt-solo-answer-a-description = Your answer
+opt-solo-hint-name = hint
+opt-solo-hint-description = Get a hint
+opt-solo-giveup-name = giveup
+opt-solo-giveup-description = Give up and see the story
+opt-solo-stats-name = stats
+opt-solo-stats-description = Show your solo mode stats
+cmd-daily-name = daily
+cmd-daily-description = Take on today's daily puzzle
+opt-daily-question-name = question
+opt-daily-question-description = Ask about today's puzzle
+opt-daily-question-q-name = question
+opt-daily-question-q-description = A question that can be answered with Yes or No
+opt-daily-answer-name = answer
+opt-daily-answer-description = Answer today's puzzle
+opt-daily-answer-a-name = answer
+opt-daily-answer-a-description = Your answer
+opt-daily-hint-name = hint
+opt-daily-hint-description = Get a hint
+opt-daily-giveup-name = giveup
+opt-daily-giveup-description = Give up on today's puzzle
+opt-daily-board-name = board
+opt-daily-board-description = Show today's results board
+cmd-team-name = team
+cmd-team-description = Form teams and play a team game
+opt-team-create-name = create
+opt-team-create-description = Create a team and join it
+opt-team-create-name-name = name
+opt-team-create-name-description = The team name
+opt-team-join-name = join
+opt-team-join-description = Join a team
+opt-team-join-name-name = name
+opt-team-join-name-description = The name of the team to join
+opt-team-list-name = list
+opt-team-list-description = Show the teams
+opt-team-play-name = play
+opt-team-play-description = Each team gets a thread with the same puzzle, and the first team to solve it wins
+cmd-library-name = library
+cmd-library-description = Show the best-rated puzzles
+cmd-submit-name = submit
+cmd-submit-description = Submit your own puzzle. Once a moderator approves it, it can be played from the library
+opt-submit-difficulty-name = difficulty
+opt-submit-difficulty-description = Difficulty of the puzzle you are submitting
+choice-submit-difficulty-easy = Easy
+choice-submit-difficulty-normal = Normal
+choice-submit-difficulty-hard = Hard
+cmd-leaderboard-name = leaderboard
+cmd-leaderboard-description = Show the ranking of correct answers
+opt-leaderboard-period-name = period
+opt-leaderboard-period-description = The period to rank (all time if omitted)
+choice-leaderboard-period-session = This session
+choice-leaderboard-period-week = This week
+choice-leaderboard-period-month = This month
+choice-leaderboard-period-all = All time
+cmd-display-name = display
+cmd-display-description = Choose how replies to questions are shown
+opt-display-mode-name = mode
+opt-display-mode-description = Display mode
+choice-display-mode-verbose = Verbose (post every reply)
+choice-display-mode-compact = Compact (group into a Q&A board)
+cmd-admin-name = admin
+cmd-admin-description = Commands for administrators
+opt-admin-reset-name = reset
+opt-admin-reset-description = Reset the session in this channel
+opt-admin-end-name = end
+opt-admin-end-description = Force the game in this channel to end
+opt-admin-reload-name = reload
+opt-admin-reload-description = Reload the prompts
+opt-admin-status-name = status
+opt-admin-status-description = Show the bot status
+opt-admin-allow-name = allow
+opt-admin-allow-description = Set the channels where games are allowed
+opt-admin-allow-channel-name = channel
+opt-admin-allow-channel-description = Target channel
+opt-admin-allow-enabled-name = enabled
+opt-admin-allow-enabled-description = True to allow, False to revoke
+opt-admin-modlog-name = modlog
+opt-admin-modlog-description = Show recent moderation records
+opt-admin-review-name = review
+opt-admin-review-description = Show submitted puzzles waiting for review
+opt-admin-config-name = config
+opt-admin-config-description = Change the game settings for a channel
+opt-admin-config-channel-name = channel
+opt-admin-config-channel-description = Target channel (defaults to this channel)
+opt-admin-config-difficulty-name = difficulty
+opt-admin-config-difficulty-description = Difficulty of the puzzles
+choice-admin-config-difficulty-easy = Easy
+choice-admin-config-difficulty-normal = Normal
+choice-admin-config-difficulty-hard = Hard
+opt-admin-config-theme-name = theme
+opt-admin-config-theme-description = Theme of the puzzles (blank to clear)
+opt-admin-config-language-name = language
+opt-admin-config-language-description = Language of the game
+choice-admin-config-language-auto = Auto (language of the user who runs /play)
+choice-admin-config-language-ja = Japanese
+choice-admin-config-language-en = English
+opt-admin-config-timer-name = timer
+opt-admin-config-timer-description = Time limit per puzzle in minutes, 0 for none
+opt-admin-config-compact-name = compact
+opt-admin-config-compact-description = Group replies to questions into the Q&A board
+opt-admin-config-participants-name = participants
+opt-admin-config-participants-description = Only users who ran /join can ask and answer
+opt-admin-config-turns-name = turns
+opt-admin-config-turns-description = Joined users take turns asking questions
+opt-admin-config-library-name = library
+opt-admin-config-library-description = Replay well-rated puzzles from the library instead of generating new ones
+
+## Gameplay
+
+joined =
+    { $name } (ID: { $id }) has joined.
+    There { $count ->
+        [one] is now { $count } participant.
+       *[other] are now { $count } participants.
+    }
+    Type /play to start the game.
+invalid-timing = This command can't be used right now
+api-error = Failed to get a response from the API
+problem-header = Here is the puzzle
+
+## Pausing
+
+game-paused = ⏸️ { $name } paused the game. Use /resume to continue
+game-paused-notice = The game is paused. Use /resume before asking questions
+game-resumed = ▶️ The game is back on!
+
+    { $problem }
+game-resumed-recap = ▶️ The game is back on!
+
+    📜 Previously on...
+    { $recap }
+
+    { $problem }
+team-pause-disabled = You can't pause a team game
+
+## Threads
+
+thread-name = Situation Puzzle
+thread-created = The puzzle has been posted in { $thread }
+thread-create-failed = Failed to create a thread
+thread-nested = A new thread can't be created inside a thread
+thread-dm = Threads can't be created in DMs
+thread-summary = The game in { $thread } has ended
+
+## Chat mode
+
+chat-enabled =
+    Chat mode is enabled.
+    Messages ending with "?" are treated as questions, and messages starting with "{ $prefixes }" as answers.
+    ✅ Yes / ❌ No / ➖ Irrelevant
+chat-disabled = Chat mode is disabled
+
+## Display mode
+
+display-verbose = Replies to questions will be posted as they are
+display-compact = Replies to questions will be grouped into the Q&A board
+board-title = Q&A board
+board-footer = { $count ->
+    [one] { $count } question
+   *[other] { $count } questions
+}
+
+## Results
+
+result-next-button = Next puzzle
+result-finish-button = Finish
+result-giveup = Too bad, you gave up 😢
+result-correct = Congratulations 🎉
+    { $user } got it right!
+result-story = The story
+hint-message = 💡 Hint { $count }: { $hint }
+result-score = #{ $rank } · { $points } pts
+score-breakdown = Points
+score-solved = Solved { $points }
+score-questions = { $count } questions -{ $points }
+score-time = { $minutes } min -{ $points }
+score-hints = { $count ->
+    [one] { $count } hint
+   *[other] { $count } hints
+} -{ $points }
+score-assist = { $count ->
+    [one] { $count } "Yes" question
+   *[other] { $count } "Yes" questions
+} +{ $points }
+score-wrong = { $count ->
+    [one] { $count } wrong answer
+   *[other] { $count } wrong answers
+} -{ $points }
+game-finished = The game is over
+unknown-component = An unknown component was triggered
+channel-not-allowed = Games can't be played in this channel
+not-your-turn = It's someone else's turn
+turn-title = Turn
+turn-notice = Next up: { $user }
+turn-timeout = { $user } ran out of time. Next up: { $next }
+not-participant = Only users who joined with /join can ask and answer
+result-timeup = Time is up ⏰
+language-name = English
+
+## Solo mode
+
+solo-not-playing = You have no solo puzzle. Start one with /solo start
+solo-already-playing = You already have a solo puzzle. Use /solo giveup before starting a new one
+solo-solved = Correct 🎉 ({ $questions ->
+    [one] { $questions } question
+   *[other] { $questions } questions
+}, { $hints ->
+    [one] { $hints } hint
+   *[other] { $hints } hints
+})
+solo-stats-title = Solo mode stats
+solo-stats-played = Played
+solo-stats-solved = Solved
+solo-stats-gave-up = Gave up
+solo-stats-average = Average questions
+solo-stats-best = Fewest questions
+solo-stats-hints = Hints
+
+## Daily puzzle
+
+daily-title = Today's daily puzzle ({ $date })
+daily-footer = Ask and answer with /daily. Only you can see your questions. The story is revealed tomorrow
+daily-not-ready = Today's daily puzzle hasn't been posted yet
+daily-finished = You're done with today's daily puzzle. Come back tomorrow!
+daily-solved = Correct 🎉 ({ $questions ->
+    [one] { $questions } question
+   *[other] { $questions } questions
+}, { $streak ->
+    [one] { $streak } day streak
+   *[other] { $streak } day streak
+})
+    The story is revealed tomorrow!
+daily-gave-up = You gave up. The story is revealed tomorrow
+daily-reveal-title = Daily puzzle ({ $date }) answer
+daily-board-title = Results
+daily-board-heading = Daily puzzle ({ $date }) results
+daily-board-line = **{ $rank }.** { $name } — { $questions ->
+    [one] { $questions } question
+   *[other] { $questions } questions
+} · { $hints ->
+    [one] { $hints } hint
+   *[other] { $hints } hints
+} · { $streak } day streak
+daily-board-empty = Nobody has solved it yet
+
+## Team games
+
+team-created = { $user } created the team "{ $team }"
+team-exists = The team "{ $team }" already exists
+team-joined = { $user } joined the team "{ $team }" ({ $count ->
+    [one] { $count } member
+   *[other] { $count } members
+})
+team-not-found = The team "{ $team }" was not found
+team-in-progress = Teams can't be changed during a team game
+team-list-title = Teams
+team-list-empty = There are no teams yet. Create one with /team create
+team-list-line = **{ $team }** ({ $score } pts): { $members }
+team-not-enough = A team game needs at least two teams
+team-threads-failed = Could not create the team threads. Check the bot's permissions and try again
+team-game-started = The team game has started! Ask your questions in your team's thread
+    { $threads }
+team-thread-name = Team "{ $team }"
+team-thread-welcome = { $members }
+    This is the thread for the team "{ $team }". Solve the puzzle before the other teams!
+team-won = 🏆 The team "{ $team }" wins!
+team-too-late = So close! Another team solved it first
+team-finished = The team "{ $team }" solved the puzzle, so this game is over. See { $channel } for the results
+team-giveup-disabled = You can't give up in a team game
+team-play-in-thread = During a team game, ask questions and answer in your team's thread
+team-result-title = Team game results
+team-result-winner = 🏆 { $user } of the team "{ $team }" solved the puzzle!
+team-result-value = { $score } pts ({ $count ->
+    [one] { $count } question
+   *[other] { $count } questions
+})
+
+## Puzzle ratings
+
+rating-unfair = Unfair
+rating-easy = Too easy
+rating-nonsense = Story didn't make sense
+rating-thanks = Thanks for rating!
+rating-not-found = This puzzle could not be found
+library-title = Best-rated puzzles
+library-empty = No puzzles have been rated yet
+library-line = **#{ $id }** ★{ $stars } ({ $count ->
+    [one] { $count } rating
+   *[other] { $count } ratings
+}, { $rate }% solved) { $problem }
+
+## Puzzle submissions
+
+submission-modal-title = Submit a puzzle
+submission-modal-title-label = Title
+submission-modal-title-placeholder = e.g. The seaside restaurant
+submission-modal-problem-label = Puzzle
+submission-modal-solution-label = Story (solution)
+submission-modal-hints-label = Hints (optional)
+submission-modal-hints-placeholder = One per line, in the order they should be given
+submission-invalid = Please fill in the title, puzzle and story
+submission-received = Thanks for submitting "{ $title }"! A moderator will review it soon
+submission-none-pending = There are no submissions waiting for review
+submission-pending-count = { $count ->
+    [one] { $count } submission waiting for review
+   *[other] { $count } submissions waiting for review
+}
+submission-review-title = Submission #{ $id }: { $title }
+submission-author = Author
+submission-status = Status
+submission-solution = Story
+submission-hints = Hints
+submission-status-pending = Pending
+submission-status-approved = Approved
+submission-status-rejected = Rejected
+submission-approve-button = Approve
+submission-reject-button = Reject
+submission-already-reviewed = This submission has already been reviewed
+submission-reviewed-by = Reviewed by { $name }
+submission-approved-dm = Your puzzle "{ $title }" was approved and added to the library!
+submission-rejected-dm = Your puzzle "{ $title }" was not accepted this time
+submission-credit = 📝 "{ $title }" by { $author }
+
+## Transcripts
+
+transcript-title = Situation Puzzle #{ $id }
+transcript-title-current = Situation Puzzle (in progress)
+transcript-started = Started
+transcript-ending = Result
+transcript-ending-solved = Solved by { $name }
+transcript-ending-gaveup = Gave up
+transcript-ending-timeup = Time's up
+transcript-ending-playing = In progress
+transcript-problem = Puzzle
+transcript-questions = Questions
+transcript-no-questions = No questions were asked
+transcript-hints = Hints
+export-not-found = There is no game to export
+export-recent = Recent games in this channel: { $games }
+import-too-large = The file is too large
+import-failed = Could not read the record. Please attach a JSON file created with /export
+import-no-solution = Records exported during a game don't include the story, so the puzzle can't be played again
+import-replay = 📂 Playing the imported puzzle
+import-summary-title = Summary of record #{ $id }
+import-summary-questions = Questions
+import-summary-verdicts = ✅ { $yes } ❌ { $no } ➖ { $irrelevant }
+import-summary-duration = Duration
+import-summary-askers = Asked by
+
+## Terminal mode
+
+cli-usage = Usage: situation-puzzle-cli [--lang ja|en] [--difficulty easy|normal|hard] [--theme THEME]
+cli-missing-api-key = Please set the OPENAI_API_KEY environment variable
+cli-player = You
+cli-generating = Creating a puzzle…
+cli-missing-text = Type your question or answer after the command (e.g. q Was the man alone?)
+cli-unknown = Unknown command. Type help to see the list of commands
+cli-help =
+    Situation Puzzle (terminal mode)
+      play (p)          Start a new puzzle
+      q <question>      Ask a question
+      a <answer>        Give an answer
+      hint (h)          Get a hint
+      giveup (g)        Give up and see the story
+      solution (s)      Show the story of the current puzzle (for debugging)
+      help              Show this list
+      quit              Exit
+
+## Leaderboard
+
+leaderboard-title-session = Leaderboard (this session)
+leaderboard-title-week = Leaderboard (this week)
+leaderboard-title-month = Leaderboard (this month)
+leaderboard-title-all = Leaderboard (all time)
+leaderboard-title-last-week = Last week's leaderboard
+leaderboard-line = **{ $rank }.** { $name } — { $solves ->
+    [one] { $solves } solve
+   *[other] { $solves } solves
+} · { $average } questions on average · { $streak }-day streak
+leaderboard-empty = No puzzles have been solved yet
+leaderboard-footer = Page { $page } of { $pages }
+
+## Administration
+
+admin-forbidden = You don't have permission to use this command
+admin-reset = The session in this channel has been reset
+admin-no-game = There is no game in progress in this channel
+admin-end = The game has been ended
+admin-reload = The prompts and scoring rules have been reloaded. They will be used from the next puzzle
+admin-allow-added = Games are now allowed in { $channel } ({ $count } allowed channels)
+admin-allow-removed = { $channel } is no longer allowed ({ $count } allowed channels)
+admin-status-title = Bot status
+admin-status-uptime = Uptime
+admin-status-sessions = Sessions
+admin-status-sessions-value = { $playing } playing / { $total } total
+admin-status-channels = Allowed channels
+admin-status-all-channels = All channels
+admin-config-title = Channel settings
+admin-config-difficulty = Difficulty
+admin-config-theme = Theme
+admin-config-language = Language
+admin-config-timer = Time limit
+admin-config-compact = Compact display
+admin-config-participants = Participants only
+admin-config-turns = Turns
+admin-config-library = Replay from library
+admin-config-none = None
+admin-config-auto = Auto
+admin-config-on = On
+admin-config-off = Off
+admin-config-minutes = { $minutes ->
+    [one] { $minutes } minute
+   *[other] { $minutes } minutes
+}
+admin-modlog-title = Moderation log
+admin-modlog-empty = There are no moderation records
+
+## Moderation
+
+moderation-blocked = Your message may contain inappropriate content and was not sent
+moderation-warned = ⚠ Your message may contain inappropriate content. It has been recorded
+moderation-puzzle-blocked = Failed to generate a puzzle. Please try again
+puzzle-rejected = Couldn't come up with a good enough puzzle. Please try again
+moderation-story-hidden = (The story may contain inappropriate content and can't be shown)
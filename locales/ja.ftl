@@ -0,0 +1,434 @@
+## コマンド
+
+cmd-play-name = 出題
+cmd-play-description = ゲームスタート
+opt-play-thread-name = スレッド
+opt-play-thread-description = 問題専用のスレッドを作成します
+choice-play-thread-none = 作成しない
+choice-play-thread-public = 公開スレッド
+choice-play-thread-private = プライベートスレッド
+cmd-join-name = 参加
+cmd-join-description = 参加
+cmd-question-name = 質問
+cmd-question-description = 質問を送信します
+opt-question-q-name = 質問
+opt-question-q-description = 質問の内容を入力してください
+cmd-answer-name = 回答
+cmd-answer-description = 回答を送信します
+opt-answer-a-name = 回答
+opt-answer-a-description = 回答の内容を入力してください
+cmd-giveup-name = ギブアップ
+cmd-giveup-description = ゲームを終了します
+cmd-hint-name = ヒント
+cmd-hint-description = ヒントをもらいます（正解したときの得点が減ります）
+cmd-pause-name = 一時停止
+cmd-pause-description = ゲームを一時停止します。制限時間も止まります
+cmd-resume-name = 再開
+cmd-resume-description = 一時停止したゲームを再開します
+cmd-chat-name = チャット
+cmd-chat-description = 通常のメッセージでの質問・回答を切り替えます
+opt-chat-enabled-name = 有効
+opt-chat-enabled-description = 有効にする場合はTrue
+cmd-export-name = 書き出し
+cmd-export-description = 問題のやりとりの記録をファイルで書き出します
+opt-export-format-name = 形式
+opt-export-format-description = ファイルの形式（省略時は両方）
+choice-export-format-markdown = Markdown（共有用）
+choice-export-format-json = JSON（読み込み・分析用）
+opt-export-game-name = ゲーム
+opt-export-game-description = 書き出すゲームの番号（省略時は出題中か直前のゲーム）
+cmd-import-name = 読み込み
+cmd-import-description = 書き出した記録（JSON）を読み込んで集計を表示します
+opt-import-file-name = ファイル
+opt-import-file-description = /書き出し で作ったJSONファイル
+opt-import-play-name = 出題
+opt-import-play-description = 読み込んだ問題をこのチャンネルで出題し直す
+cmd-solo-name = ソロ
+cmd-solo-description = 自分だけに表示されるソロモードで練習します
+opt-solo-start-name = 開始
+opt-solo-start-description = ソロモードの問題を出題します
+opt-solo-start-difficulty-name = 難易度
+opt-solo-start-difficulty-description = 出題する問題の難易度
+choice-solo-start-difficulty-easy = やさしい
+choice-solo-start-difficulty-normal = ふつう
+choice-solo-start-difficulty-hard = むずかしい
+opt-solo-question-name = 質問
+opt-solo-question-description = 質問します
+opt-solo-question-q-name = 質問
+opt-solo-question-q-description = YesかNoで答えられる質問
+opt-solo-answer-name = 回答
+opt-solo-answer-description = 回答します
+opt-solo-answer-a-name = 回答
+opt-solo-answer-a-description = 回答の内容
+opt-solo-hint-name = ヒント
+opt-solo-hint-description = ヒントをもらいます
+opt-solo-giveup-name = ギブアップ
+opt-solo-giveup-description = ギブアップしてストーリーを見ます
+opt-solo-stats-name = 成績
+opt-solo-stats-description = ソロモードの成績を表示します
+cmd-daily-name = デイリー
+cmd-daily-description = 今日のデイリーパズルに挑戦します
+opt-daily-question-name = 質問
+opt-daily-question-description = 今日の問題について質問します
+opt-daily-question-q-name = 質問
+opt-daily-question-q-description = YesかNoで答えられる質問
+opt-daily-answer-name = 回答
+opt-daily-answer-description = 今日の問題に回答します
+opt-daily-answer-a-name = 回答
+opt-daily-answer-a-description = 回答の内容
+opt-daily-hint-name = ヒント
+opt-daily-hint-description = ヒントをもらいます
+opt-daily-giveup-name = ギブアップ
+opt-daily-giveup-description = 今日の問題をギブアップします
+opt-daily-board-name = 結果
+opt-daily-board-description = 今日の結果ボードを表示します
+cmd-team-name = チーム
+cmd-team-description = チームを作ってチーム戦で遊びます
+opt-team-create-name = 作成
+opt-team-create-description = チームを作成して参加します
+opt-team-create-name-name = 名前
+opt-team-create-name-description = チームの名前
+opt-team-join-name = 参加
+opt-team-join-description = チームに参加します
+opt-team-join-name-name = 名前
+opt-team-join-name-description = 参加するチームの名前
+opt-team-list-name = 一覧
+opt-team-list-description = チームの一覧を表示します
+opt-team-play-name = 対戦
+opt-team-play-description = チームごとのスレッドで同じ問題に挑み、先に正解したチームの勝ちです
+cmd-library-name = ライブラリ
+cmd-library-description = 評価の高い問題を表示します
+cmd-submit-name = 投稿
+cmd-submit-description = 自作の問題を投稿します。モデレーターが承認するとライブラリから出題されます
+opt-submit-difficulty-name = 難易度
+opt-submit-difficulty-description = 投稿する問題の難易度
+choice-submit-difficulty-easy = やさしい
+choice-submit-difficulty-normal = ふつう
+choice-submit-difficulty-hard = むずかしい
+cmd-leaderboard-name = ランキング
+cmd-leaderboard-description = 正解数のランキングを表示します
+opt-leaderboard-period-name = 期間
+opt-leaderboard-period-description = 集計する期間（省略時は全期間）
+choice-leaderboard-period-session = このセッション
+choice-leaderboard-period-week = 今週
+choice-leaderboard-period-month = 今月
+choice-leaderboard-period-all = 全期間
+cmd-display-name = 表示
+cmd-display-description = 質問への返答の表示方法を切り替えます
+opt-display-mode-name = モード
+opt-display-mode-description = 表示方法
+choice-display-mode-verbose = 詳細（返答をそのまま表示）
+choice-display-mode-compact = コンパクト（Q&Aボードにまとめる）
+cmd-admin-name = 管理
+cmd-admin-description = 管理者用のコマンドです
+opt-admin-reset-name = リセット
+opt-admin-reset-description = このチャンネルのセッションをリセットします
+opt-admin-end-name = 強制終了
+opt-admin-end-description = このチャンネルのゲームを強制終了します
+opt-admin-reload-name = プロンプト再読み込み
+opt-admin-reload-description = プロンプトを読み込み直します
+opt-admin-status-name = ステータス
+opt-admin-status-description = Botの状態を表示します
+opt-admin-allow-name = 許可チャンネル
+opt-admin-allow-description = ゲームを許可するチャンネルを設定します
+opt-admin-allow-channel-name = チャンネル
+opt-admin-allow-channel-description = 対象のチャンネル
+opt-admin-allow-enabled-name = 許可
+opt-admin-allow-enabled-description = 許可する場合はTrue、取り消す場合はFalse
+opt-admin-modlog-name = モデレーションログ
+opt-admin-modlog-description = 最近のモデレーションの記録を表示します
+opt-admin-review-name = 審査
+opt-admin-review-description = 審査待ちの投稿された問題を表示します
+opt-admin-config-name = チャンネル設定
+opt-admin-config-description = チャンネルごとのゲームの設定を変更します
+opt-admin-config-channel-name = チャンネル
+opt-admin-config-channel-description = 対象のチャンネル（省略時はこのチャンネル）
+opt-admin-config-difficulty-name = 難易度
+opt-admin-config-difficulty-description = 出題する問題の難易度
+choice-admin-config-difficulty-easy = やさしい
+choice-admin-config-difficulty-normal = ふつう
+choice-admin-config-difficulty-hard = むずかしい
+opt-admin-config-theme-name = テーマ
+opt-admin-config-theme-description = 出題する問題のテーマ（空白で解除）
+opt-admin-config-language-name = 言語
+opt-admin-config-language-description = ゲームの言語
+choice-admin-config-language-auto = 自動（/playを実行したユーザーの言語）
+choice-admin-config-language-ja = 日本語
+choice-admin-config-language-en = 英語
+opt-admin-config-timer-name = 制限時間
+opt-admin-config-timer-description = 1問あたりの制限時間（分）。0で無制限
+opt-admin-config-compact-name = コンパクト表示
+opt-admin-config-compact-description = 質問への返答をQ&Aボードにまとめる
+opt-admin-config-participants-name = 参加者限定
+opt-admin-config-participants-description = /joinしたユーザーだけが質問・回答できる
+opt-admin-config-turns-name = 手番制
+opt-admin-config-turns-description = /joinしたユーザーが順番に質問する
+opt-admin-config-library-name = ライブラリ
+opt-admin-config-library-description = 生成する代わりに、ライブラリの評価の高い問題を出題する
+
+## ゲーム進行
+
+joined =
+    { $name } さん(ID: { $id })が参加しました。
+    現在の参加者数は{ $count }人です。
+    ゲームを開始するには/playを入力してください
+invalid-timing = 実行するタイミングが正しくありません
+api-error = APIの返却値取得においてエラーが発生しました
+problem-header = 問題です
+
+## 一時停止
+
+game-paused = ⏸️ { $name } さんがゲームを一時停止しました。/resume で再開できます
+game-paused-notice = ゲームは一時停止中です。/resume で再開してから質問してください
+game-resumed = ▶️ ゲームを再開します！
+
+    { $problem }
+game-resumed-recap = ▶️ ゲームを再開します！
+
+    📜 前回までのあらすじ
+    { $recap }
+
+    { $problem }
+team-pause-disabled = チーム戦では一時停止できません
+
+## スレッド
+
+thread-name = ウミガメのスープ
+thread-created = { $thread } で問題を出題しました
+thread-create-failed = スレッドの作成に失敗しました
+thread-nested = スレッドの中では新しいスレッドを作成できません
+thread-dm = DMではスレッドを作成できません
+thread-summary = { $thread } のゲームが終了しました
+
+## チャットモード
+
+chat-enabled =
+    チャットモードを有効にしました。
+    「？」で終わるメッセージは質問、「{ $prefixes }」で始まるメッセージは回答として扱います。
+    ✅ はい / ❌ いいえ / ➖ 関係ありません
+chat-disabled = チャットモードを無効にしました
+
+## 表示モード
+
+display-verbose = 質問への返答をそのまま表示します
+display-compact = 質問への返答をQ&Aボードにまとめて表示します
+board-title = Q&Aボード
+board-footer = 質問数 { $count }
+
+## 結果
+
+result-next-button = 次の問題に進む
+result-finish-button = 終了する
+result-giveup = 残念、ギブアップです😢
+result-correct = おめでとうございます🎉
+    { $user }さん正解です！
+result-story = 問題のストーリー
+hint-message = 💡 ヒント{ $count }: { $hint }
+result-score = { $rank }位 ・ { $points }点
+score-breakdown = 得点
+score-solved = 正解 { $points }
+score-questions = 質問{ $count }回 -{ $points }
+score-time = { $minutes }分 -{ $points }
+score-hints = ヒント{ $count }回 -{ $points }
+score-assist = 「はい」の質問{ $count }回 +{ $points }
+score-wrong = 誤答{ $count }回 -{ $points }
+game-finished = ゲームを終了します
+unknown-component = 未知のコンポーネントが呼ばれました
+channel-not-allowed = このチャンネルではゲームを遊べません
+not-your-turn = 今は他の人の番です
+turn-title = 手番
+turn-notice = 次は { $user } さんの番です
+turn-timeout = { $user } さんの持ち時間が過ぎました。次は { $next } さんの番です
+not-participant = /joinで参加したユーザーだけが質問・回答できます
+result-timeup = 時間切れです⏰
+language-name = 日本語
+
+## ソロモード
+
+solo-not-playing = ソロモードの問題が出題されていません。/solo 開始 で始めてください
+solo-already-playing = ソロモードの問題が出題中です。新しい問題にするには先に /solo ギブアップ してください
+solo-solved = 正解です 🎉（質問{ $questions }回・ヒント{ $hints }回）
+solo-stats-title = ソロモードの成績
+solo-stats-played = 挑戦
+solo-stats-solved = 正解
+solo-stats-gave-up = ギブアップ
+solo-stats-average = 平均質問数
+solo-stats-best = 最少質問数
+solo-stats-hints = ヒント
+
+## デイリーパズル
+
+daily-title = 今日のデイリーパズル（{ $date }）
+daily-footer = /daily で質問・回答できます。やりとりは自分にだけ表示されます。ストーリーは明日公開します
+daily-not-ready = 今日のデイリーパズルはまだ出題されていません
+daily-finished = 今日のデイリーパズルは終了しています。また明日挑戦してください
+daily-solved = 正解です 🎉（質問{ $questions }回・連続正解{ $streak }日）
+    ストーリーは明日公開します。お楽しみに！
+daily-gave-up = ギブアップしました。ストーリーは明日公開します
+daily-reveal-title = デイリーパズル（{ $date }）の答え
+daily-board-title = 結果ボード
+daily-board-heading = デイリーパズル（{ $date }）の結果
+daily-board-line = **{ $rank }.** { $name } ー 質問{ $questions }回 ・ ヒント{ $hints }回 ・ 連続{ $streak }日
+daily-board-empty = まだ正解した人はいません
+
+## チーム戦
+
+team-created = { $user } さんがチーム「{ $team }」を作成しました
+team-exists = チーム「{ $team }」はすでにあります
+team-joined = { $user } さんがチーム「{ $team }」に参加しました（{ $count }人）
+team-not-found = チーム「{ $team }」が見つかりません
+team-in-progress = チーム戦の最中はチームを変更できません
+team-list-title = チーム
+team-list-empty = まだチームがありません。/team create で作成してください
+team-list-line = **{ $team }**（{ $score }点）: { $members }
+team-not-enough = チーム戦には2チーム以上必要です
+team-threads-failed = チームのスレッドを作成できませんでした。ボットの権限を確認して、もう一度お試しください
+team-game-started = チーム戦を開始しました！各チームのスレッドで質問してください
+    { $threads }
+team-thread-name = チーム「{ $team }」
+team-thread-welcome = { $members }
+    チーム「{ $team }」のスレッドです。他のチームより先に正解しましょう！
+team-won = 🏆 チーム「{ $team }」の勝利です！
+team-too-late = 惜しい！他のチームが先に正解しました
+team-finished = チーム「{ $team }」が正解したため終了しました。結果は { $channel } をご覧ください
+team-giveup-disabled = チーム戦ではギブアップできません
+team-play-in-thread = チーム戦の最中は各チームのスレッドで質問・回答してください
+team-result-title = チーム戦の結果
+team-result-winner = 🏆 チーム「{ $team }」の { $user } さんが正解しました！
+team-result-value = { $score }点（質問{ $count }回）
+
+## 問題の評価
+
+rating-unfair = 理不尽
+rating-easy = 簡単すぎ
+rating-nonsense = 腑に落ちない
+rating-thanks = 評価ありがとうございます！
+rating-not-found = この問題は見つかりませんでした
+library-title = 評価の高い問題
+library-empty = まだ評価された問題はありません
+library-line = **#{ $id }** ★{ $stars }（{ $count }件）正解率{ $rate }% { $problem }
+
+## 問題の投稿
+
+submission-modal-title = 問題の投稿
+submission-modal-title-label = タイトル
+submission-modal-title-placeholder = 例: 海辺のレストラン
+submission-modal-problem-label = 問題文
+submission-modal-solution-label = ストーリー（解答）
+submission-modal-hints-label = ヒント（任意）
+submission-modal-hints-placeholder = 1行に1つずつ、出す順に書いてください
+submission-invalid = タイトル・問題文・ストーリーを入力してください
+submission-received = 「{ $title }」を投稿しました！モデレーターの審査をお待ちください
+submission-none-pending = 審査待ちの投稿はありません
+submission-pending-count = 審査待ち: { $count }件
+submission-review-title = 投稿 #{ $id }「{ $title }」
+submission-author = 作者
+submission-status = 状態
+submission-solution = ストーリー
+submission-hints = ヒント
+submission-status-pending = 審査待ち
+submission-status-approved = 承認済み
+submission-status-rejected = 却下
+submission-approve-button = 承認
+submission-reject-button = 却下
+submission-already-reviewed = この投稿はすでに審査されています
+submission-reviewed-by = { $name } さんが審査しました
+submission-approved-dm = 投稿した問題「{ $title }」が承認され、ライブラリに追加されました！
+submission-rejected-dm = 投稿した問題「{ $title }」は今回は採用されませんでした
+submission-credit = 📝 「{ $title }」 作: { $author }
+
+## 記録の書き出し
+
+transcript-title = ウミガメのスープ #{ $id }
+transcript-title-current = ウミガメのスープ（出題中）
+transcript-started = 出題
+transcript-ending = 結果
+transcript-ending-solved = { $name } さんが正解
+transcript-ending-gaveup = ギブアップ
+transcript-ending-timeup = 時間切れ
+transcript-ending-playing = 出題中
+transcript-problem = 問題
+transcript-questions = 質問
+transcript-no-questions = 質問はありません
+transcript-hints = ヒント
+export-not-found = 書き出せるゲームがありません
+export-recent = このチャンネルの最近のゲーム: { $games }
+import-too-large = ファイルが大きすぎます
+import-failed = 記録を読み込めませんでした。/書き出し で作ったJSONファイルを指定してください
+import-no-solution = 出題中に書き出した記録にはストーリーが含まれないため、出題し直せません
+import-replay = 📂 読み込んだ問題を出題します
+import-summary-title = 記録 #{ $id } の集計
+import-summary-questions = 質問
+import-summary-verdicts = ✅ { $yes } ❌ { $no } ➖ { $irrelevant }
+import-summary-duration = 所要時間
+import-summary-askers = 質問した人
+
+## ターミナル版
+
+cli-usage = 使い方: situation-puzzle-cli [--lang ja|en] [--difficulty easy|normal|hard] [--theme テーマ]
+cli-missing-api-key = 環境変数 OPENAI_API_KEY を設定してください
+cli-player = あなた
+cli-generating = 問題を作成しています…
+cli-missing-text = コマンドのあとに質問・回答を入力してください（例: q 男は一人でしたか？）
+cli-unknown = 不明なコマンドです。help でコマンドの一覧を表示します
+cli-help =
+    ウミガメのスープ（ターミナル版）
+      play（p）         新しい問題を出題します
+      q <質問>          質問します
+      a <回答>          回答します
+      hint（h）         ヒントをもらいます
+      giveup（g）       ギブアップしてストーリーを見ます
+      solution（s）     出題中の問題のストーリーを表示します（動作確認用）
+      help              この一覧を表示します
+      quit              終了します
+
+## ランキング
+
+leaderboard-title-session = ランキング（このセッション）
+leaderboard-title-week = ランキング（今週）
+leaderboard-title-month = ランキング（今月）
+leaderboard-title-all = ランキング（全期間）
+leaderboard-title-last-week = 先週のランキング
+leaderboard-line = **{ $rank }.** { $name } ー { $solves }問正解 ・ 平均質問数 { $average } ・ 連続 { $streak }日
+leaderboard-empty = まだ正解の記録がありません
+leaderboard-footer = { $page } / { $pages } ページ
+
+## 管理
+
+admin-forbidden = このコマンドを実行する権限がありません
+admin-reset = このチャンネルのセッションをリセットしました
+admin-no-game = このチャンネルで進行中のゲームはありません
+admin-end = ゲームを強制終了しました
+admin-reload = プロンプトと得点のルールを読み込み直しました。次の出題から反映されます
+admin-allow-added = { $channel } でのゲームを許可しました（許可チャンネル数: { $count }）
+admin-allow-removed = { $channel } の許可を取り消しました（許可チャンネル数: { $count }）
+admin-status-title = Botの状態
+admin-status-uptime = 稼働時間
+admin-status-sessions = セッション
+admin-status-sessions-value = 進行中 { $playing } / 全体 { $total }
+admin-status-channels = 許可チャンネル
+admin-status-all-channels = すべてのチャンネル
+admin-config-title = チャンネル設定
+admin-config-difficulty = 難易度
+admin-config-theme = テーマ
+admin-config-language = 言語
+admin-config-timer = 制限時間
+admin-config-compact = コンパクト表示
+admin-config-participants = 参加者限定
+admin-config-turns = 手番制
+admin-config-library = ライブラリから出題
+admin-config-none = なし
+admin-config-auto = 自動
+admin-config-on = オン
+admin-config-off = オフ
+admin-config-minutes = { $minutes }分
+admin-modlog-title = モデレーションログ
+admin-modlog-empty = モデレーションの記録はありません
+
+## モデレーション
+
+moderation-blocked = 不適切な内容が含まれている可能性があるため送信できませんでした
+moderation-warned = ⚠ 不適切な内容が含まれている可能性があります。記録されました
+moderation-puzzle-blocked = 問題を生成できませんでした。もう一度お試しください
+puzzle-rejected = 納得のいく問題を作れませんでした。もう一度お試しください
+moderation-story-hidden = （不適切な内容が含まれている可能性があるため、ストーリーは表示できません）
@@ -0,0 +1,13 @@
+use serenity::async_trait;
+
+use crate::models::ChatCompletionMessage;
+
+// 会話履歴を渡して応答を受け取るバックエンド
+// 本番ではOpenAIのAPIを使い、テストでは決まった応答を返すものに差し替える
+#[async_trait]
+pub trait ChatBackend: Send + Sync {
+    async fn send_request(
+        &self,
+        messages: &[ChatCompletionMessage],
+    ) -> Result<String, anyhow::Error>;
+}
@@ -1,3 +1,7 @@
+pub mod backend;
 pub mod openai_client;
+#[cfg(test)]
+pub mod scripted;
 
+pub use backend::ChatBackend;
 pub use openai_client::OpenAIClient;
@@ -1,15 +1,39 @@
 use reqwest::Client;
 use serde_json::{json, Value};
+use serenity::async_trait;
+use tracing::debug;
 
+use crate::api::ChatBackend;
 use crate::models::ChatCompletionMessage;
 
+const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+const DEFAULT_MODEL: &str = "gpt-4o-mini";
+
+#[derive(Clone)]
 pub struct OpenAIClient {
     pub api_key: String,
+    pub base_url: String,
+    pub model: String,
 }
 
 impl OpenAIClient {
     pub fn new(api_key: String) -> Self {
-        Self { api_key }
+        Self {
+            api_key,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            model: DEFAULT_MODEL.to_string(),
+        }
+    }
+
+    // OpenAI互換のAPI（ローカルのLLMなど）を使う場合はURLとモデルを指定する
+    pub fn with_endpoint(mut self, base_url: Option<String>, model: Option<String>) -> Self {
+        if let Some(base_url) = base_url {
+            self.base_url = base_url.trim_end_matches('/').to_string();
+        }
+        if let Some(model) = model {
+            self.model = model;
+        }
+        self
     }
 
     pub async fn send_request(
@@ -18,12 +42,12 @@ impl OpenAIClient {
     ) -> Result<String, anyhow::Error> {
         let client = Client::new();
         let body = json!({
-            "model": "gpt-4o-mini",
+            "model": self.model,
             "messages": messages,
         });
 
         let request = client
-            .post("https://api.openai.com/v1/chat/completions")
+            .post(format!("{}/chat/completions", self.base_url))
             .header("Authorization", format!("Bearer {}", self.api_key))
             .json(&body)
             .build()?;
@@ -33,9 +57,9 @@ impl OpenAIClient {
         let headers = response.headers().clone();
         let json: Value = response.json().await?;
 
-        println!("Status: {}", status);
-        println!("Headers: {:?}", headers);
-        println!("Response JSON: {}", json);
+        debug!("Status: {}", status);
+        debug!("Headers: {:?}", headers);
+        debug!("Response JSON: {}", json);
 
         if status.is_success() {
             let message = json
@@ -54,4 +78,52 @@ impl OpenAIClient {
             ))
         }
     }
+
+    // モデレーションAPIで判定し、該当したカテゴリを返す
+    pub async fn moderate(&self, input: &str) -> Result<Vec<String>, anyhow::Error> {
+        let client = Client::new();
+        let body = json!({
+            "model": "omni-moderation-latest",
+            "input": input,
+        });
+
+        let response = client
+            .post(format!("{}/moderations", self.base_url))
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(&body)
+            .send()
+            .await?;
+        let status = response.status();
+        let json: Value = response.json().await?;
+
+        if !status.is_success() {
+            return Err(anyhow::anyhow!(
+                "エラーが発生しました: ステータスコード {}",
+                status
+            ));
+        }
+
+        let categories = json
+            .get("results")
+            .and_then(|results| results.get(0))
+            .and_then(|result| result.get("categories"))
+            .and_then(|categories| categories.as_object())
+            .ok_or_else(|| anyhow::anyhow!("レスポンスの形式が意図したものではありません"))?
+            .iter()
+            .filter(|(_, flagged)| flagged.as_bool().unwrap_or_default())
+            .map(|(category, _)| category.clone())
+            .collect();
+
+        Ok(categories)
+    }
+}
+
+#[async_trait]
+impl ChatBackend for OpenAIClient {
+    async fn send_request(
+        &self,
+        messages: &[ChatCompletionMessage],
+    ) -> Result<String, anyhow::Error> {
+        OpenAIClient::send_request(self, messages).await
+    }
 }
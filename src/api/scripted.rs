@@ -0,0 +1,37 @@
+use std::sync::Mutex;
+
+use serenity::async_trait;
+
+use crate::api::ChatBackend;
+use crate::models::ChatCompletionMessage;
+
+type Script = Box<dyn Fn(&[ChatCompletionMessage]) -> String + Send + Sync>;
+
+// 受け取った会話履歴から応答を決めるテスト用のバックエンド
+// 送られたリクエストは `requests` に記録する
+pub struct ScriptedBackend {
+    script: Script,
+    pub requests: Mutex<Vec<Vec<ChatCompletionMessage>>>,
+}
+
+impl ScriptedBackend {
+    pub fn new(
+        script: impl Fn(&[ChatCompletionMessage]) -> String + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            script: Box::new(script),
+            requests: Mutex::new(vec![]),
+        }
+    }
+}
+
+#[async_trait]
+impl ChatBackend for ScriptedBackend {
+    async fn send_request(
+        &self,
+        messages: &[ChatCompletionMessage],
+    ) -> Result<String, anyhow::Error> {
+        self.requests.lock().unwrap().push(messages.to_vec());
+        Ok((self.script)(messages))
+    }
+}
@@ -0,0 +1,193 @@
+// ターミナルで遊ぶための対話モード
+// Discordのトークンなしで、ボットと同じ問題の生成・判定を試せる。プロンプトの調整やゲームマスターの動作確認に使う
+//
+// 設定は環境変数で渡す: OPENAI_API_KEY（必須）, OPENAI_BASE_URL, OPENAI_MODEL, PROMPT_DIR, PUZZLE_ATTEMPTS, DATA_DIR
+// 引数: --lang <ja|en> --difficulty <easy|normal|hard> --theme <テーマ>
+
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+
+use fluent::fluent_args;
+use serenity::all::ChannelId;
+
+use situation_puzzle::api::OpenAIClient;
+use situation_puzzle::constants::prompt::load_system_prompts;
+use situation_puzzle::critique::Critic;
+use situation_puzzle::game::{Event, Game, GameError, Player};
+use situation_puzzle::i18n::{t, t_args, Locale};
+use situation_puzzle::library::Library;
+use situation_puzzle::models::{ChannelSettings, Difficulty};
+use situation_puzzle::moderation::{Moderation, ModerationAction, Moderator};
+use situation_puzzle::storage::Store;
+use situation_puzzle::utils::question_generator::{generate_puzzle, Generator};
+use situation_puzzle::utils::question_message::question_message;
+
+// モデレーションの記録などに使うチャンネル。ターミナルにはチャンネルがないため固定の値にする
+const CLI_CHANNEL: ChannelId = ChannelId::new(1);
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let mut locale = Locale::default();
+    let mut settings = ChannelSettings::default();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next()) {
+            // 対応していない値は既定の値で続けず、使い方を表示して終了する
+            ("--lang", Some(value)) if Locale::from_discord(&value).is_some() => {
+                locale = Locale::from_discord(&value).unwrap_or(locale);
+            }
+            ("--difficulty", Some(value)) if Difficulty::parse(&value).is_some() => {
+                settings.difficulty = Difficulty::parse(&value).unwrap_or_default();
+            }
+            ("--theme", Some(value)) => settings.theme = Some(value),
+            _ => {
+                eprintln!("{}", t(locale, "cli-usage"));
+                return;
+            }
+        }
+    }
+    settings.locale = Some(locale);
+
+    let Some(api_key) = env::var("OPENAI_API_KEY")
+        .ok()
+        .filter(|key| !key.is_empty())
+    else {
+        eprintln!("{}", t(locale, "cli-missing-api-key"));
+        return;
+    };
+    let client = OpenAIClient::new(api_key).with_endpoint(
+        env::var("OPENAI_BASE_URL")
+            .ok()
+            .filter(|url| !url.is_empty()),
+        env::var("OPENAI_MODEL")
+            .ok()
+            .filter(|model| !model.is_empty()),
+    );
+    // ボットのライブラリと混ざらないよう、既定ではボットとは別のディレクトリに保存する
+    let store = Store::new(env::var("DATA_DIR").unwrap_or_else(|_| "data/cli".to_string()));
+    let generator = Generator {
+        client: client.clone(),
+        moderation: Arc::new(Moderation::new(
+            Moderator::None,
+            ModerationAction::Block,
+            ModerationAction::Regenerate,
+            store.clone(),
+        )),
+        library: Arc::new(Library::new(store.clone())),
+        critic: Arc::new(Critic::new(store)),
+        attempts: env::var("PUZZLE_ATTEMPTS")
+            .ok()
+            .and_then(|attempts| attempts.parse().ok())
+            .unwrap_or(3),
+    };
+    let system_prompts = load_system_prompts(env::var("PROMPT_DIR").ok().as_deref());
+
+    let player = Player::new(0, t(locale, "cli-player"));
+    let mut game = Game::new(locale);
+
+    println!("{}", t(locale, "cli-help"));
+    let stdin = io::stdin();
+    loop {
+        print!("> ");
+        let _ = io::stdout().flush();
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+        let line = line.trim();
+        let (command, text) = line.split_once(' ').unwrap_or((line, ""));
+        let text = text.trim();
+
+        match command {
+            "" => {}
+            "question" | "q" | "answer" | "a" if text.is_empty() => {
+                println!("{}", t(locale, "cli-missing-text"));
+            }
+            "play" | "p" => {
+                println!("{}", t(locale, "cli-generating"));
+                // 出題中でも新しい問題に切り替える
+                let _ = game.finish();
+                let _ = game.generate();
+                let question = generate_puzzle(
+                    &generator,
+                    system_prompts[&locale].clone(),
+                    &mut game,
+                    &settings,
+                    CLI_CHANNEL,
+                )
+                .await;
+                println!("{}", question_message(&question, locale));
+                if question.is_ok() {
+                    // 出題済みの問題として覚えておき、次の生成で重複を避ける
+                    generator
+                        .library
+                        .add_generated(&mut game, settings.difficulty, CLI_CHANNEL)
+                        .await;
+                    let _ = game.start();
+                } else {
+                    game.rollback();
+                }
+            }
+            "question" | "q" => match game.question(&client, &player, text).await {
+                Ok(Event::Replied { verdict, reply }) => println!("{} {}", verdict.emoji(), reply),
+                result => print_error(result, locale),
+            },
+            "answer" | "a" => match game.answer(&client, &player, text).await {
+                Ok(Event::Solved { .. }) => {
+                    let solved = t_args(
+                        locale,
+                        "solo-solved",
+                        &fluent_args![
+                            "questions" => game.qa_log.len(),
+                            "hints" => game.hints.len()
+                        ],
+                    );
+                    println!("{}\n\n{}", solved, story(&game, locale));
+                    let _ = game.reveal();
+                }
+                Ok(Event::Incorrect { reply }) => println!("{}", reply),
+                result => print_error(result, locale),
+            },
+            "hint" | "h" => match game.hint(&client).await {
+                Ok(Event::Hinted { count, hint }) => {
+                    let message = t_args(
+                        locale,
+                        "hint-message",
+                        &fluent_args!["count" => count, "hint" => hint],
+                    );
+                    println!("{}", message);
+                }
+                result => print_error(result, locale),
+            },
+            "giveup" | "g" => match game.give_up() {
+                Ok(_) => {
+                    println!("{}\n\n{}", t(locale, "result-giveup"), story(&game, locale));
+                    let _ = game.reveal();
+                }
+                Err(e) => print_error(Err(e.into()), locale),
+            },
+            // ゲームマスターの動作確認用に、出題中でもストーリーを表示する
+            "solution" | "s" if !game.solution.is_empty() => {
+                println!("{}", story(&game, locale));
+            }
+            "solution" | "s" => println!("{}", t(locale, "invalid-timing")),
+            "help" => println!("{}", t(locale, "cli-help")),
+            "quit" | "exit" => break,
+            _ => println!("{}", t(locale, "cli-unknown")),
+        }
+    }
+}
+
+// 出題中でない場合と、ゲームマスターへのリクエストに失敗した場合の表示
+fn print_error(result: Result<Event, GameError>, locale: Locale) {
+    match result {
+        Err(GameError::Transition(_)) => println!("{}", t(locale, "invalid-timing")),
+        Err(GameError::Backend(_)) => println!("{}", t(locale, "api-error")),
+        Ok(_) => {}
+    }
+}
+
+fn story(game: &Game, locale: Locale) -> String {
+    format!("{}\n{}", t(locale, "result-story"), game.solution)
+}
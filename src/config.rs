@@ -1,14 +1,33 @@
 // 設定関連の構造体や設定読み込みロジック
 // 環境変数や設定ファイルからの読み込みを行う
 
-use serenity::model::id::GuildId;
+use chrono::FixedOffset;
+use serenity::model::id::{ChannelId, GuildId, RoleId};
 use shuttle_runtime::SecretStore;
 
+use crate::moderation::ModerationAction;
+
 #[derive(Clone)]
 pub struct Config {
     pub discord_token: String,
     pub discord_guild_id: GuildId,
     pub openai_api_key: String,
+    pub openai_base_url: Option<String>,
+    pub openai_model: Option<String>,
+    pub answer_prefixes: Vec<String>,
+    pub moderator_role_id: Option<RoleId>,
+    pub prompt_dir: Option<String>,
+    pub data_dir: String,
+    pub moderation: String,
+    pub moderation_words: Vec<String>,
+    pub moderation_action: ModerationAction,
+    pub puzzle_moderation_action: ModerationAction,
+    pub leaderboard_channel_id: Option<ChannelId>,
+    pub timezone: FixedOffset,
+    pub daily_channel_id: Option<ChannelId>,
+    pub daily_hour: u32,
+    pub puzzle_attempts: usize,
+    pub review_channel_id: Option<ChannelId>,
 }
 
 impl Config {
@@ -28,6 +47,124 @@ impl Config {
             openai_api_key: secrets
                 .get("OPENAI_API_KEY")
                 .expect("'OPENAI_API_KEY' was not found"),
+            // OpenAI互換のAPIを使う場合のURL（例: http://localhost:11434/v1）
+            openai_base_url: secrets.get("OPENAI_BASE_URL").filter(|url| !url.is_empty()),
+            // 使うモデル（省略時は gpt-4o-mini）
+            openai_model: secrets
+                .get("OPENAI_MODEL")
+                .filter(|model| !model.is_empty()),
+            // メッセージでの回答に使う接頭辞（カンマ区切り）
+            answer_prefixes: secrets
+                .get("ANSWER_PREFIXES")
+                .unwrap_or_else(|| "A:,A：,回答:,回答：".to_string())
+                .split(',')
+                .map(|prefix| prefix.trim().to_string())
+                .filter(|prefix| !prefix.is_empty())
+                .collect(),
+            // /adminを実行できるロール（サーバー管理権限を持つユーザーは常に実行できる）
+            moderator_role_id: secrets
+                .get("MODERATOR_ROLE_ID")
+                .filter(|id| !id.is_empty())
+                .map(|id| RoleId::new(id.parse::<u64>().expect("MODERATOR_ROLE_ID parse failed"))),
+            // システムプロンプトを上書きするファイル（system.ja.txt など）を置くディレクトリ
+            prompt_dir: secrets.get("PROMPT_DIR").filter(|dir| !dir.is_empty()),
+            // チャンネル設定などを保存するディレクトリ
+            data_dir: secrets
+                .get("DATA_DIR")
+                .filter(|dir| !dir.is_empty())
+                .unwrap_or_else(|| "data".to_string()),
+            // openai / wordlist / none
+            moderation: secrets
+                .get("MODERATION")
+                .unwrap_or_else(|| "none".to_string()),
+            moderation_words: secrets
+                .get("MODERATION_WORDS")
+                .unwrap_or_default()
+                .split(',')
+                .map(|word| word.trim().to_string())
+                .filter(|word| !word.is_empty())
+                .collect(),
+            // ユーザーの質問・回答が該当した場合の対応（block / warn）
+            moderation_action: moderation_action(secrets, "MODERATION_ACTION")
+                .unwrap_or(ModerationAction::Block),
+            // 生成した問題・ストーリーが該当した場合の対応（block / warn / regenerate）
+            puzzle_moderation_action: moderation_action(secrets, "PUZZLE_MODERATION_ACTION")
+                .unwrap_or(ModerationAction::Regenerate),
+            // 毎週のランキングを投稿するチャンネル
+            leaderboard_channel_id: secrets
+                .get("LEADERBOARD_CHANNEL_ID")
+                .filter(|id| !id.is_empty())
+                .map(|id| {
+                    ChannelId::new(
+                        id.parse::<u64>()
+                            .expect("LEADERBOARD_CHANNEL_ID parse failed"),
+                    )
+                }),
+            // ランキングの週・月の区切りに使うUTCからの時差（時間）
+            timezone: secrets
+                .get("TIMEZONE_OFFSET")
+                .filter(|hours| !hours.is_empty())
+                .map(|hours| {
+                    hours
+                        .parse::<i32>()
+                        .ok()
+                        .and_then(|hours| hours.checked_mul(3600))
+                        .and_then(FixedOffset::east_opt)
+                        .expect("TIMEZONE_OFFSET parse failed")
+                })
+                .unwrap_or_else(|| FixedOffset::east_opt(9 * 3600).unwrap()),
+            // デイリーパズルを投稿するチャンネル
+            daily_channel_id: secrets
+                .get("DAILY_CHANNEL_ID")
+                .filter(|id| !id.is_empty())
+                .map(|id| {
+                    ChannelId::new(id.parse::<u64>().expect("DAILY_CHANNEL_ID parse failed"))
+                }),
+            // デイリーパズルを投稿する時刻（TIMEZONE_OFFSETの時差での時、0〜23）
+            daily_hour: secrets
+                .get("DAILY_HOUR")
+                .filter(|hour| !hour.is_empty())
+                .map(|hour| {
+                    hour.parse::<u32>()
+                        .ok()
+                        .filter(|hour| *hour < 24)
+                        .expect("DAILY_HOUR parse failed")
+                })
+                .unwrap_or(9),
+            // 生成した問題がモデレーション・重複・批評で作り直しになった場合に試す回数
+            puzzle_attempts: secrets
+                .get("PUZZLE_ATTEMPTS")
+                .filter(|attempts| !attempts.is_empty())
+                .map(|attempts| {
+                    attempts
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|attempts| *attempts > 0)
+                        .expect("PUZZLE_ATTEMPTS parse failed")
+                })
+                .unwrap_or(3),
+            // 投稿された問題の審査を依頼するチャンネル
+            review_channel_id: secrets
+                .get("REVIEW_CHANNEL_ID")
+                .filter(|id| !id.is_empty())
+                .map(|id| {
+                    ChannelId::new(id.parse::<u64>().expect("REVIEW_CHANNEL_ID parse failed"))
+                }),
         }
     }
 }
+
+// モデレーションの対応の設定。書き間違いで意図しない対応にならないよう、読めない値では起動を止める
+fn moderation_action(secrets: &SecretStore, key: &str) -> Option<ModerationAction> {
+    secrets
+        .get(key)
+        .filter(|action| !action.is_empty())
+        .map(|action| {
+            ModerationAction::parse(&action).unwrap_or_else(|| {
+                panic!(
+                    "{} must be one of block / warn / regenerate, but was '{}'",
+                    key, action
+                )
+            })
+        })
+}
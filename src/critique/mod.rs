@@ -0,0 +1,167 @@
+// 生成した問題の品質チェック
+// 出題する前に、別のリクエストで問題と解答の矛盾・解けるかどうか・答えが一つに定まるかを批評させる
+// 不合格になった下書きは、プロンプトの調整に使えるよう理由と一緒に記録する
+
+use std::fmt;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::error;
+
+use crate::api::ChatBackend;
+use crate::constants::prompt::GamePrompt;
+use crate::i18n::Locale;
+use crate::models::{ChatCompletionMessage, Role};
+use crate::storage::Store;
+
+// 保存する下書きの上限
+const DRAFT_LIMIT: usize = 200;
+// 不合格の場合に批評の応答の先頭に書かせる印
+const REJECTED: &str = "NG";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Review {
+    Accepted,
+    // 不合格の理由
+    Rejected(String),
+}
+
+// 何度作り直しても批評で合格する問題を作れなかったことを表すエラー
+#[derive(Debug)]
+pub struct Rejected;
+
+impl fmt::Display for Rejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "批評で合格する問題を作れませんでした")
+    }
+}
+
+impl std::error::Error for Rejected {}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RejectedDraft {
+    // UNIX時間（秒）
+    pub timestamp: i64,
+    pub locale: Locale,
+    pub problem: String,
+    pub solution: String,
+    pub reason: String,
+}
+
+// 批評役へのリクエスト。出題の会話履歴とは別に送る
+pub fn critique_request(
+    prompt: &GamePrompt,
+    problem: &str,
+    solution: &str,
+) -> Vec<ChatCompletionMessage> {
+    vec![
+        ChatCompletionMessage::new(
+            Role::System,
+            prompt.critique_system_prompt(problem, solution),
+        ),
+        ChatCompletionMessage::new(Role::User, prompt.critique.to_string()),
+    ]
+}
+
+pub async fn critique(
+    backend: &dyn ChatBackend,
+    prompt: &GamePrompt,
+    problem: &str,
+    solution: &str,
+) -> Result<Review, anyhow::Error> {
+    let messages = critique_request(prompt, problem, solution);
+    let reply = backend.send_request(&messages).await?;
+
+    Ok(parse_review(&reply))
+}
+
+// 先頭がNGなら不合格、それ以外は合格とする
+fn parse_review(reply: &str) -> Review {
+    let reply = reply.trim_start_matches(|c: char| c.is_whitespace() || matches!(c, '*' | '#'));
+    match reply.strip_prefix(REJECTED) {
+        Some(reason) => Review::Rejected(
+            reason
+                .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, ':' | '：' | '*'))
+                .trim()
+                .to_string(),
+        ),
+        None => Review::Accepted,
+    }
+}
+
+pub struct Critic {
+    drafts: Mutex<Vec<RejectedDraft>>,
+    store: Store,
+}
+
+impl Critic {
+    pub fn new(store: Store) -> Self {
+        Self {
+            drafts: Mutex::new(store.load("rejected_drafts")),
+            store,
+        }
+    }
+
+    pub async fn reject(&self, locale: Locale, problem: &str, solution: &str, reason: String) {
+        let mut drafts = self.drafts.lock().await;
+        drafts.push(RejectedDraft {
+            timestamp: Utc::now().timestamp(),
+            locale,
+            problem: problem.to_string(),
+            solution: solution.to_string(),
+            reason,
+        });
+        if drafts.len() > DRAFT_LIMIT {
+            let overflow = drafts.len() - DRAFT_LIMIT;
+            drafts.drain(..overflow);
+        }
+
+        if let Err(e) = self.store.save("rejected_drafts", &*drafts) {
+            error!("不合格の問題の保存に失敗しました: {:?}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ok_is_accepted() {
+        assert_eq!(parse_review("OK"), Review::Accepted);
+        assert_eq!(parse_review("OK\n矛盾はありません。"), Review::Accepted);
+    }
+
+    #[test]
+    fn ng_is_rejected_with_reason() {
+        assert_eq!(
+            parse_review("NG: 解答が問題文と矛盾しています"),
+            Review::Rejected("解答が問題文と矛盾しています".to_string())
+        );
+        assert_eq!(
+            parse_review("NG：答えが一つに定まりません"),
+            Review::Rejected("答えが一つに定まりません".to_string())
+        );
+        // Markdownで装飾された応答
+        assert_eq!(
+            parse_review("**NG**: The story is missing.\n"),
+            Review::Rejected("The story is missing.".to_string())
+        );
+        assert_eq!(parse_review("\n## NG"), Review::Rejected(String::new()));
+    }
+
+    #[test]
+    fn malformed_reply_is_accepted() {
+        // 形式に従わない応答で出題を止めないよう、NGで始まらない応答は合格として扱う
+        assert_eq!(parse_review(""), Review::Accepted);
+        assert_eq!(
+            parse_review("This puzzle looks fine to me."),
+            Review::Accepted
+        );
+        assert_eq!(
+            parse_review("問題ありません。NGではありません。"),
+            Review::Accepted
+        );
+    }
+}
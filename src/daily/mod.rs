@@ -0,0 +1,271 @@
+// 毎日の問題（デイリーパズル）と、その日の結果の記録
+// 問題はサーバーで一日一問で、参加者はそれぞれ自分専用のセッションで同じ問題に挑む
+// 一日の区切りは投稿する時刻で、ストーリーは次の問題を投稿するときに公開する
+
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serenity::all::UserId;
+use tokio::sync::Mutex;
+use tracing::error;
+
+use crate::i18n::Locale;
+use crate::storage::Store;
+
+// 保存しておく問題の数
+const PUZZLE_LIMIT: usize = 30;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DailyPuzzle {
+    pub date: NaiveDate,
+    pub locale: Locale,
+    pub problem: String,
+    pub solution: String,
+    // ストーリーと結果を公開したかどうか
+    #[serde(default)]
+    pub revealed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DailyResult {
+    pub date: NaiveDate,
+    pub user_id: UserId,
+    pub user_name: String,
+    pub questions: usize,
+    pub hints: usize,
+    // ギブアップした場合はfalse
+    pub solved: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct DailyRecords {
+    puzzles: Vec<DailyPuzzle>,
+    results: Vec<DailyResult>,
+}
+
+// 結果ボードの1行分
+pub struct DailyStanding {
+    pub user_name: String,
+    pub questions: usize,
+    pub hints: usize,
+    // この日まで毎日正解し続けた日数
+    pub streak: usize,
+}
+
+pub struct Daily {
+    records: Mutex<DailyRecords>,
+    store: Store,
+    offset: FixedOffset,
+    // 問題を投稿する時刻（時）
+    hour: u32,
+}
+
+impl Daily {
+    pub fn new(store: Store, offset: FixedOffset, hour: u32) -> Self {
+        Self {
+            records: Mutex::new(store.load("daily")),
+            store,
+            offset,
+            hour,
+        }
+    }
+
+    // 今の時刻が属する問題の日付。投稿する時刻より前は前日の問題になる
+    pub fn today(&self) -> NaiveDate {
+        (Utc::now().with_timezone(&self.offset) - Duration::hours(self.hour as i64)).date_naive()
+    }
+
+    // 次に問題を投稿する時刻
+    pub fn next_post_time(&self) -> DateTime<FixedOffset> {
+        let date = self.today() + Duration::days(1);
+        self.offset
+            .from_local_datetime(&date.and_hms_opt(self.hour, 0, 0).unwrap_or_default())
+            .single()
+            .unwrap_or_else(|| Utc::now().with_timezone(&self.offset) + Duration::days(1))
+    }
+
+    // 今日の問題。まだ投稿していない場合はNone
+    pub async fn current(&self) -> Option<DailyPuzzle> {
+        let today = self.today();
+        self.records
+            .lock()
+            .await
+            .puzzles
+            .iter()
+            .rev()
+            .find(|puzzle| puzzle.date == today)
+            .cloned()
+    }
+
+    // 公開していない過去の問題
+    pub async fn unrevealed(&self) -> Vec<DailyPuzzle> {
+        let today = self.today();
+        self.records
+            .lock()
+            .await
+            .puzzles
+            .iter()
+            .filter(|puzzle| puzzle.date < today && !puzzle.revealed)
+            .cloned()
+            .collect()
+    }
+
+    pub async fn add_puzzle(&self, puzzle: DailyPuzzle) {
+        let mut records = self.records.lock().await;
+        records.puzzles.push(puzzle);
+        if records.puzzles.len() > PUZZLE_LIMIT {
+            let overflow = records.puzzles.len() - PUZZLE_LIMIT;
+            records.puzzles.drain(..overflow);
+        }
+        self.save(&records);
+    }
+
+    pub async fn mark_revealed(&self, date: NaiveDate) {
+        let mut records = self.records.lock().await;
+        for puzzle in records
+            .puzzles
+            .iter_mut()
+            .filter(|puzzle| puzzle.date == date)
+        {
+            puzzle.revealed = true;
+        }
+        self.save(&records);
+    }
+
+    pub async fn result(&self, date: NaiveDate, user_id: UserId) -> Option<DailyResult> {
+        self.records
+            .lock()
+            .await
+            .results
+            .iter()
+            .find(|result| result.date == date && result.user_id == user_id)
+            .cloned()
+    }
+
+    pub async fn record(&self, result: DailyResult) {
+        let mut records = self.records.lock().await;
+        records.results.push(result);
+        self.save(&records);
+    }
+
+    // 指定した日に正解した人を、質問数・ヒント数が少ない順に並べる
+    pub async fn board(&self, date: NaiveDate) -> Vec<DailyStanding> {
+        let records = self.records.lock().await;
+        let mut standings: Vec<_> = records
+            .results
+            .iter()
+            .filter(|result| result.date == date && result.solved)
+            .map(|result| DailyStanding {
+                user_name: result.user_name.clone(),
+                questions: result.questions,
+                hints: result.hints,
+                streak: streak(&records.results, result.user_id, date),
+            })
+            .collect();
+
+        standings.sort_by(|a, b| {
+            a.questions
+                .cmp(&b.questions)
+                .then(a.hints.cmp(&b.hints))
+                .then(a.user_name.cmp(&b.user_name))
+        });
+        standings
+    }
+
+    pub async fn streak(&self, user_id: UserId, date: NaiveDate) -> usize {
+        streak(&self.records.lock().await.results, user_id, date)
+    }
+
+    // 指定した日に正解した場合の連続正解日数。正解を記録する前の返答に使う
+    pub async fn streak_if_solved(&self, user_id: UserId, date: NaiveDate) -> usize {
+        let records = self.records.lock().await;
+        1 + date
+            .pred_opt()
+            .map_or(0, |yesterday| streak(&records.results, user_id, yesterday))
+    }
+
+    fn save(&self, records: &DailyRecords) {
+        if let Err(e) = self.store.save("daily", records) {
+            error!("デイリーパズルの記録の保存に失敗しました: {:?}", e);
+        }
+    }
+}
+
+// 指定した日から遡って、毎日正解し続けた日数
+fn streak(results: &[DailyResult], user_id: UserId, date: NaiveDate) -> usize {
+    let solved = |date: NaiveDate| {
+        results
+            .iter()
+            .any(|result| result.user_id == user_id && result.date == date && result.solved)
+    };
+
+    let mut days = 0;
+    while solved(date - Duration::days(days as i64)) {
+        days += 1;
+    }
+    days
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER: UserId = UserId::new(1);
+
+    fn result(user_id: UserId, date: &str, solved: bool) -> DailyResult {
+        DailyResult {
+            date: date.parse().unwrap(),
+            user_id,
+            user_name: String::new(),
+            questions: 5,
+            hints: 0,
+            solved,
+        }
+    }
+
+    fn date(text: &str) -> NaiveDate {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn consecutive_days_are_counted() {
+        let results = vec![
+            result(USER, "2026-10-16", true),
+            result(USER, "2026-10-17", true),
+            result(USER, "2026-10-18", true),
+            // 他の人の記録は数えない
+            result(UserId::new(2), "2026-10-15", true),
+        ];
+        assert_eq!(streak(&results, USER, date("2026-10-18")), 3);
+        assert_eq!(streak(&results, USER, date("2026-10-17")), 2);
+    }
+
+    #[test]
+    fn gap_or_give_up_ends_the_streak() {
+        let results = vec![
+            result(USER, "2026-10-14", true),
+            result(USER, "2026-10-15", true),
+            // 10月16日は遊んでいない
+            result(USER, "2026-10-17", true),
+            result(USER, "2026-10-18", false),
+        ];
+        assert_eq!(streak(&results, USER, date("2026-10-17")), 1);
+        assert_eq!(streak(&results, USER, date("2026-10-18")), 0);
+    }
+
+    #[tokio::test]
+    async fn today_not_yet_played() {
+        let dir = std::env::temp_dir().join(format!("daily-{}", std::process::id()));
+        let daily = Daily::new(Store::new(dir), FixedOffset::east_opt(0).unwrap(), 0);
+        daily.records.lock().await.results = vec![
+            result(USER, "2026-10-17", true),
+            result(USER, "2026-10-18", true),
+        ];
+
+        // 今日はまだ正解していないので連続日数は0だが、正解すれば昨日までの分に続く
+        let today = date("2026-10-19");
+        assert_eq!(daily.streak(USER, today).await, 0);
+        assert_eq!(daily.streak_if_solved(USER, today).await, 3);
+        assert_eq!(daily.streak_if_solved(UserId::new(2), today).await, 1);
+    }
+}
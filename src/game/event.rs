@@ -0,0 +1,56 @@
+use std::fmt;
+
+use crate::game::{Player, TransitionError};
+use crate::models::Verdict;
+
+// ゲームの操作の結果。表示の仕方は呼び出し元（Discord・ターミナルなど）が決める
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    // 参加者の募集を始めた
+    Joined,
+    // 問題の用意を始めた
+    Generating,
+    // 出題した
+    Started { problem: String },
+    // 質問に返答した
+    Replied { verdict: Verdict, reply: String },
+    // 正解した。ストーリーは Game::solution から表示する
+    Solved { solver: Player },
+    // 不正解だった
+    Incorrect { reply: String },
+    // ヒントを出した。countは出したヒントの数
+    Hinted { count: usize, hint: String },
+    GaveUp,
+    TimedOut,
+    Paused,
+    Resumed,
+    // 結果を表示し、次の問題を待っている
+    Revealed,
+    Finished,
+}
+
+// ゲームの操作に失敗した理由
+#[derive(Debug)]
+pub enum GameError {
+    // 今の状態ではできない操作
+    Transition(TransitionError),
+    // ゲームマスターへのリクエストに失敗した
+    Backend(anyhow::Error),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::Transition(e) => write!(f, "{}", e),
+            GameError::Backend(e) => write!(f, "ゲームマスターへのリクエストに失敗しました: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+impl From<TransitionError> for GameError {
+    fn from(e: TransitionError) -> Self {
+        GameError::Transition(e)
+    }
+}
@@ -0,0 +1,595 @@
+// 出題中の問題の進行（質問・回答・ヒント・ギブアップ）
+// Discordに依存しないため、ボット・ターミナル・テストから同じように動かせる
+// 表示や権限の確認は呼び出し元で行い、このモジュールは状態の更新と結果（Event）だけを扱う
+//
+// 状態は遷移表（State::next）にある操作でしか変えない
+// 遷移の後の処理（問題の生成・結果の投稿など）に失敗した場合は、呼び出し元が rollback で元の状態に戻す
+// 後の処理に成功したら commit する。出題（start）と結果の表示（reveal）のように、それ自体が後の処理の完了を表す遷移は自動で commit する
+
+pub mod event;
+pub mod player;
+pub mod state;
+
+pub use event::{Event, GameError};
+pub use player::{Player, PlayerId};
+pub use state::{State, Transition, TransitionError};
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::api::ChatBackend;
+use crate::constants::prompt::game_prompt;
+use crate::i18n::Locale;
+use crate::judge::{self, Kind};
+use crate::models::{ChatCompletionMessage, Role, Verdict};
+
+// 出題中の問題に対する質問と返答の記録
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QaEntry {
+    pub asker: PlayerId,
+    #[serde(default)]
+    pub asker_name: String,
+    pub question: String,
+    pub verdict: Verdict,
+    pub reply: String,
+    // 質問した時刻（UNIX時間）
+    #[serde(default)]
+    pub asked_at: i64,
+}
+
+// 1つの問題の状態
+// 一時停止したゲームはボットを再起動しても続けられるよう保存する
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Game {
+    pub locale: Locale,
+    state: State,
+    // 直前の遷移の前の状態。rollback で戻す
+    #[serde(skip)]
+    previous: Option<State>,
+    // 出題したゲームマスターとの会話履歴
+    pub messages: Vec<ChatCompletionMessage>,
+    // 出題中の問題文
+    pub problem: String,
+    // 出題中の問題のストーリーと模範解答。判定役にだけ渡す
+    pub solution: String,
+    pub qa_log: Vec<QaEntry>,
+    // 出題中の問題で出したヒント
+    pub hints: Vec<String>,
+    // 投稿された問題の場合に作者が用意したヒント。生成より先に順に出す
+    pub author_hints: Vec<String>,
+    // 出題中の問題でのプレイヤーごとの誤答の回数
+    pub wrong_answers: HashMap<PlayerId, u32>,
+    // 出題した時刻。得点の計算に使う
+    // 一時停止していた時間を得点の計算に含めないよう、再開するときに止まっていた分だけ遅らせる
+    pub puzzle_started_at: i64,
+    // 一時停止した時刻（UNIX時間）
+    #[serde(default)]
+    paused_at: Option<i64>,
+    // ライブラリに記録した場合はその問題のID
+    pub puzzle_id: Option<u64>,
+    // ライブラリに記録しない問題（読み込んだ記録から出題したものなど）
+    #[serde(default)]
+    pub unlisted: bool,
+}
+
+impl Game {
+    pub fn new(locale: Locale) -> Self {
+        Self {
+            locale,
+            state: State::Idle,
+            previous: None,
+            messages: vec![],
+            problem: String::new(),
+            solution: String::new(),
+            qa_log: vec![],
+            hints: vec![],
+            author_hints: vec![],
+            wrong_answers: HashMap::new(),
+            puzzle_started_at: 0,
+            paused_at: None,
+            puzzle_id: None,
+            unlisted: false,
+        }
+    }
+
+    // 同じ問題を別の場所（チーム戦のスレッドなど）で続けるためのゲーム
+    // 質問の記録やヒントは引き継がない
+    pub fn fork(&self) -> Self {
+        Self {
+            state: self.state,
+            messages: self.messages.clone(),
+            problem: self.problem.clone(),
+            solution: self.solution.clone(),
+            puzzle_started_at: self.puzzle_started_at,
+            unlisted: self.unlisted,
+            ..Self::new(self.locale)
+        }
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.state == State::Playing
+    }
+
+    // 遷移表に従って状態を変える
+    fn apply(&mut self, transition: Transition) -> Result<(), TransitionError> {
+        let next = self.state.next(transition)?;
+        if next != self.state {
+            self.previous = Some(self.state);
+            self.state = next;
+        }
+        Ok(())
+    }
+
+    // 直前の遷移を取り消す。遷移の後の処理に失敗した場合に使う
+    // 再開を取り消した場合は、その時点からまた一時停止しているものとする
+    pub fn rollback(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            if previous == State::Paused && self.paused_at.is_none() {
+                self.paused_at = Some(Utc::now().timestamp());
+            }
+            self.state = previous;
+        }
+    }
+
+    // 遷移の後の処理に成功した。以降の rollback で古い状態に戻らないようにする
+    pub fn commit(&mut self) {
+        self.previous = None;
+    }
+
+    // 後の処理がない遷移
+    fn apply_and_commit(&mut self, transition: Transition) -> Result<(), TransitionError> {
+        self.apply(transition)?;
+        self.commit();
+        Ok(())
+    }
+
+    // 会話履歴を指定したロケールのシステムプロンプトだけにし、前の問題の記録を破棄する
+    pub fn reset(&mut self, locale: Locale, system_prompt: String) {
+        self.locale = locale;
+        self.messages.clear();
+        self.messages
+            .push(ChatCompletionMessage::new(Role::System, system_prompt));
+        self.problem.clear();
+        self.solution.clear();
+        self.qa_log.clear();
+        self.hints.clear();
+        self.author_hints.clear();
+        self.wrong_answers.clear();
+        self.puzzle_id = None;
+        self.unlisted = false;
+        self.puzzle_started_at = Utc::now().timestamp();
+        self.paused_at = None;
+    }
+
+    // ゲームマスターにリクエストを送り、やりとりを会話履歴に追加する
+    pub async fn request(
+        &mut self,
+        backend: &dyn ChatBackend,
+        content: String,
+    ) -> Result<String, anyhow::Error> {
+        self.messages
+            .push(ChatCompletionMessage::new(Role::User, content));
+
+        match backend.send_request(&self.messages).await {
+            Ok(res) => {
+                self.messages
+                    .push(ChatCompletionMessage::new(Role::Assistant, res.clone()));
+                Ok(res)
+            }
+            Err(e) => {
+                self.messages.pop();
+                Err(e)
+            }
+        }
+    }
+
+    // 参加者の募集を始める。募集中に参加した場合はそのまま
+    pub fn join(&mut self) -> Result<Event, TransitionError> {
+        self.apply_and_commit(Transition::Join)?;
+        Ok(Event::Joined)
+    }
+
+    // 問題の用意（生成・ライブラリ・読み込み）を始める
+    pub fn generate(&mut self) -> Result<Event, TransitionError> {
+        self.apply(Transition::Generate)?;
+        Ok(Event::Generating)
+    }
+
+    // 用意した問題文と解答で出題を始める
+    pub fn start(&mut self) -> Result<Event, TransitionError> {
+        self.apply_and_commit(Transition::Start)?;
+        Ok(Event::Started {
+            problem: self.problem.clone(),
+        })
+    }
+
+    // 質問を判定させて記録する
+    pub async fn question(
+        &mut self,
+        backend: &dyn ChatBackend,
+        asker: &Player,
+        question: &str,
+    ) -> Result<Event, GameError> {
+        self.state.next(Transition::Question)?;
+        let reply = self.judge(backend, Kind::Question, question).await?;
+        let verdict = Verdict::parse(&reply, game_prompt(self.locale));
+        self.qa_log.push(QaEntry {
+            asker: asker.id,
+            asker_name: asker.name.clone(),
+            question: question.to_string(),
+            verdict,
+            reply: reply.clone(),
+            asked_at: Utc::now().timestamp(),
+        });
+        Ok(Event::Replied { verdict, reply })
+    }
+
+    // 回答を判定させる。正解した場合は結果の表示に進む
+    pub async fn answer(
+        &mut self,
+        backend: &dyn ChatBackend,
+        answerer: &Player,
+        answer: &str,
+    ) -> Result<Event, GameError> {
+        self.state.next(Transition::Answer)?;
+        let reply = self.judge(backend, Kind::Answer, answer).await?;
+        if game_prompt(self.locale).is_correct(&reply) {
+            self.apply(Transition::Solve)?;
+            Ok(Event::Solved {
+                solver: answerer.clone(),
+            })
+        } else {
+            *self.wrong_answers.entry(answerer.id).or_default() += 1;
+            Ok(Event::Incorrect { reply })
+        }
+    }
+
+    // ヒントを出す。投稿された問題は作者のヒントを使い切るまでそちらを出す
+    pub async fn hint(&mut self, backend: &dyn ChatBackend) -> Result<Event, GameError> {
+        self.state.next(Transition::Hint)?;
+        let hint = match self.author_hints.get(self.hints.len()) {
+            Some(hint) => hint.clone(),
+            None => {
+                let previous = self.hints.join("\n");
+                self.judge(backend, Kind::Hint, &previous).await?
+            }
+        };
+        self.hints.push(hint.clone());
+        Ok(Event::Hinted {
+            count: self.hints.len(),
+            hint,
+        })
+    }
+
+    pub fn give_up(&mut self) -> Result<Event, TransitionError> {
+        self.apply(Transition::GiveUp)?;
+        Ok(Event::GaveUp)
+    }
+
+    // 制限時間が過ぎた
+    pub fn time_up(&mut self) -> Result<Event, TransitionError> {
+        self.apply(Transition::TimeUp)?;
+        Ok(Event::TimedOut)
+    }
+
+    pub fn pause(&mut self) -> Result<Event, TransitionError> {
+        self.pause_at(Utc::now().timestamp())
+    }
+
+    pub fn resume(&mut self) -> Result<Event, TransitionError> {
+        self.resume_at(Utc::now().timestamp())
+    }
+
+    // 指定した時刻に一時停止する
+    pub fn pause_at(&mut self, now: i64) -> Result<Event, TransitionError> {
+        self.apply_and_commit(Transition::Pause)?;
+        self.paused_at = Some(now);
+        Ok(Event::Paused)
+    }
+
+    // 指定した時刻に再開する。止まっていた時間は経過時間に数えない
+    pub fn resume_at(&mut self, now: i64) -> Result<Event, TransitionError> {
+        self.apply(Transition::Resume)?;
+        if let Some(paused_at) = self.paused_at.take() {
+            self.puzzle_started_at += (now - paused_at).max(0);
+        }
+        Ok(Event::Resumed)
+    }
+
+    // 結果を表示し終えた
+    pub fn reveal(&mut self) -> Result<Event, TransitionError> {
+        self.apply_and_commit(Transition::Reveal)?;
+        Ok(Event::Revealed)
+    }
+
+    // ゲームを終える
+    pub fn finish(&mut self) -> Result<Event, TransitionError> {
+        self.apply_and_commit(Transition::Finish)?;
+        Ok(Event::Finished)
+    }
+
+    // 一時停止から再開するときの「前回までのあらすじ」
+    // 解答は渡さず、問題文とこれまでの質問だけからまとめさせる
+    pub async fn recap(&self, backend: &dyn ChatBackend) -> Result<String, GameError> {
+        let questions = self
+            .qa_log
+            .iter()
+            .map(|entry| format!("Q: {}\nA: {}", entry.question, entry.reply))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let messages = [
+            ChatCompletionMessage::new(
+                Role::System,
+                game_prompt(self.locale).recap_system_prompt(&self.problem, &questions),
+            ),
+            ChatCompletionMessage::new(Role::User, game_prompt(self.locale).recap.to_string()),
+        ];
+        backend
+            .send_request(&messages)
+            .await
+            .map_err(GameError::Backend)
+    }
+
+    // 解答を知っている判定役に判定させる
+    async fn judge(
+        &self,
+        backend: &dyn ChatBackend,
+        kind: Kind,
+        input: &str,
+    ) -> Result<String, GameError> {
+        judge::judge(
+            backend,
+            game_prompt(self.locale),
+            &self.problem,
+            &self.solution,
+            kind,
+            input,
+        )
+        .await
+        .map_err(GameError::Backend)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::scripted::ScriptedBackend;
+    use crate::models::ChatCompletionMessage;
+
+    // リクエストが必ず失敗するバックエンド
+    struct FailingBackend;
+
+    #[serenity::async_trait]
+    impl ChatBackend for FailingBackend {
+        async fn send_request(
+            &self,
+            _messages: &[ChatCompletionMessage],
+        ) -> Result<String, anyhow::Error> {
+            Err(anyhow::anyhow!("unavailable"))
+        }
+    }
+
+    fn player() -> Player {
+        Player::new(1, "player")
+    }
+
+    fn playing() -> Game {
+        let mut game = Game::new(Locale::Ja);
+        game.generate().unwrap();
+        game.problem = "男はバーで水を頼んだ".to_string();
+        game.solution = "男はしゃっくりを止めたかった".to_string();
+        game.start().unwrap();
+        game
+    }
+
+    #[tokio::test]
+    async fn question_is_rejected_unless_playing() {
+        let backend = ScriptedBackend::new(|_| "はい。".to_string());
+
+        let mut idle = Game::new(Locale::Ja);
+        let mut paused = playing();
+        paused.pause().unwrap();
+        for game in [&mut idle, &mut paused] {
+            let from = game.state();
+            assert!(matches!(
+                game.question(&backend, &player(), "質問").await,
+                Err(GameError::Transition(TransitionError {
+                    transition: Transition::Question,
+                    ..
+                }))
+            ));
+            assert!(matches!(
+                game.answer(&backend, &player(), "回答").await,
+                Err(GameError::Transition(_))
+            ));
+            assert!(matches!(
+                game.hint(&backend).await,
+                Err(GameError::Transition(_))
+            ));
+            assert_eq!(game.state(), from);
+            assert!(game.qa_log.is_empty());
+        }
+        assert!(backend.requests.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn question_is_recorded_while_playing() {
+        let backend = ScriptedBackend::new(|_| "はい。".to_string());
+        let mut game = playing();
+
+        let event = game.question(&backend, &player(), "質問").await.unwrap();
+        assert!(matches!(event, Event::Replied { .. }));
+        assert_eq!(game.state(), State::Playing);
+        assert_eq!(game.qa_log.len(), 1);
+        assert_eq!(game.qa_log[0].asker, PlayerId(1));
+    }
+
+    #[tokio::test]
+    async fn correct_answer_reveals_then_waits() {
+        let backend = ScriptedBackend::new(|_| "正解です。".to_string());
+        let mut game = playing();
+
+        let event = game.answer(&backend, &player(), "回答").await.unwrap();
+        assert_eq!(event, Event::Solved { solver: player() });
+        assert_eq!(game.state(), State::Revealing);
+
+        game.reveal().unwrap();
+        assert_eq!(game.state(), State::Waiting);
+        // 結果を表示した後は質問できない
+        assert!(game.question(&backend, &player(), "質問").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn incorrect_answer_keeps_playing() {
+        let backend = ScriptedBackend::new(|_| "不正解です。".to_string());
+        let mut game = playing();
+
+        for _ in 0..2 {
+            let event = game.answer(&backend, &player(), "回答").await.unwrap();
+            assert!(matches!(event, Event::Incorrect { .. }));
+        }
+        assert_eq!(game.state(), State::Playing);
+        assert_eq!(game.wrong_answers[&PlayerId(1)], 2);
+    }
+
+    #[tokio::test]
+    async fn backend_error_leaves_game_unchanged() {
+        let mut game = playing();
+
+        assert!(matches!(
+            game.question(&FailingBackend, &player(), "質問").await,
+            Err(GameError::Backend(_))
+        ));
+        assert!(matches!(
+            game.answer(&FailingBackend, &player(), "回答").await,
+            Err(GameError::Backend(_))
+        ));
+        assert!(matches!(
+            game.hint(&FailingBackend).await,
+            Err(GameError::Backend(_))
+        ));
+        assert_eq!(game.state(), State::Playing);
+        assert!(game.qa_log.is_empty());
+        assert!(game.hints.is_empty());
+        assert!(game.wrong_answers.is_empty());
+    }
+
+    #[test]
+    fn rollback_restores_previous_state() {
+        // 結果の投稿に失敗した場合
+        let mut game = playing();
+        game.give_up().unwrap();
+        assert_eq!(game.state(), State::Revealing);
+        game.rollback();
+        assert_eq!(game.state(), State::Playing);
+
+        // 問題の生成に失敗した場合
+        let mut game = Game::new(Locale::Ja);
+        game.join().unwrap();
+        game.generate().unwrap();
+        game.rollback();
+        assert_eq!(game.state(), State::Lobby);
+
+        // 取り消せるのは直前の遷移だけ
+        game.rollback();
+        assert_eq!(game.state(), State::Lobby);
+    }
+
+    #[test]
+    fn rollback_after_commit_does_nothing() {
+        // 出題した後に、問題の生成の前の状態に戻らない
+        let mut game = playing();
+        game.rollback();
+        assert_eq!(game.state(), State::Playing);
+
+        // 結果を表示した後に、ゲーム中に戻らない
+        game.give_up().unwrap();
+        game.reveal().unwrap();
+        game.rollback();
+        assert_eq!(game.state(), State::Waiting);
+
+        // 再開の後の処理に成功した場合
+        game.generate().unwrap();
+        game.start().unwrap();
+        game.pause().unwrap();
+        game.resume().unwrap();
+        game.commit();
+        game.rollback();
+        assert!(game.is_playing());
+    }
+
+    #[test]
+    fn pause_and_resume() {
+        let mut game = playing();
+        assert_eq!(game.pause(), Ok(Event::Paused));
+        assert_eq!(game.state(), State::Paused);
+        // 一時停止中は時間切れにならない
+        assert!(game.time_up().is_err());
+        assert!(game.give_up().is_err());
+        assert_eq!(game.resume(), Ok(Event::Resumed));
+        assert!(game.is_playing());
+        assert_eq!(game.time_up(), Ok(Event::TimedOut));
+    }
+
+    #[test]
+    fn paused_time_moves_the_start_forward() {
+        let mut game = playing();
+        game.puzzle_started_at = 1_000;
+        game.pause_at(1_100).unwrap();
+        game.resume_at(5_100).unwrap();
+        assert_eq!(game.puzzle_started_at, 5_000);
+
+        // 再開を取り消すと、また一時停止している状態に戻る
+        game.rollback();
+        assert_eq!(game.state(), State::Paused);
+        assert!(game.paused_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn recap_does_not_include_solution() {
+        let backend = ScriptedBackend::new(|_| "あらすじ".to_string());
+        let mut game = playing();
+        game.question(&backend, &player(), "男は怒っていましたか？")
+            .await
+            .unwrap();
+        game.pause().unwrap();
+
+        assert_eq!(game.recap(&backend).await.unwrap(), "あらすじ");
+        let requests = backend.requests.lock().unwrap();
+        let system = &requests.last().unwrap()[0].content;
+        assert!(system.contains(&game.problem));
+        assert!(system.contains("男は怒っていましたか？"));
+        assert!(!system.contains(&game.solution));
+    }
+
+    #[test]
+    fn paused_game_survives_serialization() {
+        let mut game = playing();
+        game.wrong_answers.insert(PlayerId(1), 2);
+        game.pause().unwrap();
+
+        let json = serde_json::to_string(&game).unwrap();
+        let mut restored: Game = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.state(), State::Paused);
+        assert_eq!(restored.problem, game.problem);
+        assert_eq!(restored.wrong_answers[&PlayerId(1)], 2);
+        assert_eq!(restored.resume(), Ok(Event::Resumed));
+    }
+
+    #[test]
+    fn fork_keeps_puzzle_but_not_progress() {
+        let mut game = playing();
+        game.hints.push("ヒント".to_string());
+        let fork = game.fork();
+        assert!(fork.is_playing());
+        assert_eq!(fork.problem, game.problem);
+        assert_eq!(fork.solution, game.solution);
+        assert!(fork.hints.is_empty());
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// 質問・回答したプレイヤーのID
+// DiscordのユーザーIDと同じく文字列として保存し、以前の記録（数値・文字列）も読み込めるようにする
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PlayerId(pub u64);
+
+impl Serialize for PlayerId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for PlayerId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(PlayerIdVisitor)
+    }
+}
+
+struct PlayerIdVisitor;
+
+impl Visitor<'_> for PlayerIdVisitor {
+    type Value = PlayerId;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a player id as a string or an integer")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<PlayerId, E> {
+        Ok(PlayerId(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<PlayerId, E> {
+        u64::try_from(value).map(PlayerId).map_err(E::custom)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<PlayerId, E> {
+        value.parse().map(PlayerId).map_err(E::custom)
+    }
+}
+
+// 質問・回答したプレイヤー。名前は記録と結果の表示に使う
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub id: PlayerId,
+    pub name: String,
+}
+
+impl Player {
+    pub fn new(id: u64, name: impl Into<String>) -> Self {
+        Self {
+            id: PlayerId(id),
+            name: name.into(),
+        }
+    }
+}
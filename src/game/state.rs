@@ -0,0 +1,176 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum State {
+    Idle,       // 開始前
+    Lobby,      // 参加者の募集中
+    Generating, // 問題の用意中
+    Playing,    // ゲーム中
+    Paused,     // 一時停止中
+    Revealing,  // 結果（ストーリー）の表示中
+    Waiting,    // 次の問題の待機中
+}
+
+// 状態を変える操作。質問・回答・ヒントは状態を変えないが、ゲーム中にしかできない
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    Join,
+    Generate,
+    Start,
+    Question,
+    Answer,
+    Hint,
+    Solve,
+    GiveUp,
+    TimeUp,
+    Pause,
+    Resume,
+    Reveal,
+    Finish,
+}
+
+// 今の状態ではできない操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitionError {
+    pub from: State,
+    pub transition: Transition,
+}
+
+impl fmt::Display for TransitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} の状態では {:?} できません",
+            self.from, self.transition
+        )
+    }
+}
+
+impl std::error::Error for TransitionError {}
+
+impl State {
+    // 遷移表。表にない組み合わせはエラーにする
+    pub fn next(self, transition: Transition) -> Result<State, TransitionError> {
+        use State::*;
+        use Transition::*;
+
+        let next = match (self, transition) {
+            (Idle | Lobby, Join) => Lobby,
+            (Idle | Lobby | Waiting, Generate) => Generating,
+            (Generating, Start) => Playing,
+            (Playing, Question | Answer | Hint) => Playing,
+            (Playing, Solve | GiveUp | TimeUp) => Revealing,
+            (Playing, Pause) => Paused,
+            (Paused, Resume) => Playing,
+            (Revealing, Reveal) => Waiting,
+            // 問題の用意中に止まってしまったゲームも /admin end で終えられるようにする
+            (Lobby | Generating | Playing | Paused | Revealing | Waiting, Finish) => Idle,
+            (from, transition) => return Err(TransitionError { from, transition }),
+        };
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::State::*;
+    use super::Transition::*;
+    use super::*;
+
+    const STATES: [State; 7] = [Idle, Lobby, Generating, Playing, Paused, Revealing, Waiting];
+    const TRANSITIONS: [Transition; 13] = [
+        Join, Generate, Start, Question, Answer, Hint, Solve, GiveUp, TimeUp, Pause, Resume,
+        Reveal, Finish,
+    ];
+
+    // できる操作とその後の状態。ここにない組み合わせはすべてエラーになる
+    const TABLE: &[(State, Transition, State)] = &[
+        (Idle, Join, Lobby),
+        (Idle, Generate, Generating),
+        (Lobby, Join, Lobby),
+        (Lobby, Generate, Generating),
+        (Lobby, Finish, Idle),
+        (Generating, Start, Playing),
+        (Generating, Finish, Idle),
+        (Playing, Question, Playing),
+        (Playing, Answer, Playing),
+        (Playing, Hint, Playing),
+        (Playing, Solve, Revealing),
+        (Playing, GiveUp, Revealing),
+        (Playing, TimeUp, Revealing),
+        (Playing, Pause, Paused),
+        (Playing, Finish, Idle),
+        (Paused, Resume, Playing),
+        (Paused, Finish, Idle),
+        (Revealing, Reveal, Waiting),
+        (Revealing, Finish, Idle),
+        (Waiting, Generate, Generating),
+        (Waiting, Finish, Idle),
+    ];
+
+    // 状態や操作を追加したら STATES・TRANSITIONS にも追加しないとコンパイルできないようにする
+    #[allow(dead_code)]
+    fn covers_all_variants(state: State, transition: Transition) {
+        match state {
+            Idle | Lobby | Generating | Playing | Paused | Revealing | Waiting => {}
+        }
+        match transition {
+            Join | Generate | Start | Question | Answer | Hint | Solve | GiveUp | TimeUp
+            | Pause | Resume | Reveal | Finish => {}
+        }
+    }
+
+    #[test]
+    fn transition_table_is_exhaustive() {
+        for from in STATES {
+            for transition in TRANSITIONS {
+                let expected = TABLE
+                    .iter()
+                    .find(|(state, t, _)| *state == from && *t == transition)
+                    .map(|(_, _, to)| *to);
+                match expected {
+                    Some(to) => assert_eq!(from.next(transition), Ok(to)),
+                    None => assert_eq!(
+                        from.next(transition),
+                        Err(TransitionError { from, transition }),
+                        "{:?} --{:?}--> should be rejected",
+                        from,
+                        transition
+                    ),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_state_is_reachable_from_idle() {
+        let mut reached = vec![Idle];
+        let mut index = 0;
+        while index < reached.len() {
+            for transition in TRANSITIONS {
+                if let Ok(to) = reached[index].next(transition) {
+                    if !reached.contains(&to) {
+                        reached.push(to);
+                    }
+                }
+            }
+            index += 1;
+        }
+        for state in STATES {
+            assert!(reached.contains(&state), "{:?} is unreachable", state);
+        }
+    }
+
+    #[test]
+    fn every_state_can_return_to_idle() {
+        for state in STATES {
+            let finished = state.next(Finish);
+            match state {
+                Idle => assert!(finished.is_err()),
+                _ => assert_eq!(finished, Ok(Idle), "{:?}", state),
+            }
+        }
+    }
+}
@@ -0,0 +1,385 @@
+use fluent::fluent_args;
+use serenity::all::{ChannelId, CommandInteraction, ResolvedOption, ResolvedValue};
+use serenity::builder::{CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::prelude::*;
+use tracing::error;
+
+use crate::game::State;
+use crate::handlers::Bot;
+use crate::i18n::{t, t_args, Locale};
+use crate::models::{ChannelSettings, Difficulty, DisplayMode};
+use crate::utils::submission_message::review_message;
+use crate::utils::thread::close_thread;
+
+// /admin modlog で表示する件数
+const MODLOG_LIMIT: usize = 10;
+
+// /admin のサブコマンドを実行する
+// サーバーの管理権限を持つユーザーか、設定されたモデレーターロールを持つユーザーのみ実行できる
+pub async fn handle_admin(ctx: Context, command: CommandInteraction, bot: &Bot) {
+    let locale = Locale::from_interaction(&command.locale, command.guild_locale.as_deref());
+
+    if !bot.is_moderator(command.member.as_deref()) {
+        respond_ephemeral(&ctx, &command, t(locale, "admin-forbidden")).await;
+        return;
+    }
+
+    let options = command.data.options();
+    let Some(ResolvedOption {
+        name,
+        value: ResolvedValue::SubCommand(sub_options),
+        ..
+    }) = options.first()
+    else {
+        return;
+    };
+
+    match *name {
+        "reset" => {
+            let session = bot.session(command.channel_id).await;
+            session.lock().await.reset();
+            bot.paused_games.remove(command.channel_id).await;
+
+            respond_ephemeral(&ctx, &command, t(locale, "admin-reset")).await;
+        }
+        "end" => {
+            let Some(session) = bot.find_session(command.channel_id).await else {
+                respond_ephemeral(&ctx, &command, t(locale, "admin-no-game")).await;
+                return;
+            };
+            let mut session = session.lock().await;
+            if session.game.finish().is_err() {
+                respond_ephemeral(&ctx, &command, t(locale, "admin-no-game")).await;
+                return;
+            }
+            bot.paused_games.remove(command.channel_id).await;
+
+            respond_ephemeral(&ctx, &command, t(locale, "admin-end")).await;
+
+            if let Some(parent_channel) = session.parent_channel {
+                close_thread(&ctx, command.channel_id, parent_channel, &session).await;
+                bot.remove_session(command.channel_id).await;
+            } else if let Err(why) = command
+                .channel_id
+                .say(&ctx.http, t(session.game.locale, "game-finished"))
+                .await
+            {
+                error!("Error sending message: {:?}", why);
+            }
+        }
+        "reload" => {
+            bot.reload_prompts().await;
+            bot.reload_scoring().await;
+
+            respond_ephemeral(&ctx, &command, t(locale, "admin-reload")).await;
+        }
+        "status" => {
+            let embed = status_embed(bot, locale).await;
+            let data = CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .ephemeral(true);
+
+            if let Err(why) = command
+                .create_response(&ctx.http, CreateInteractionResponse::Message(data))
+                .await
+            {
+                error!("Cannot respond to slash command: {}", why);
+            }
+        }
+        "modlog" => {
+            let embed = modlog_embed(bot, locale).await;
+            let data = CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .ephemeral(true);
+
+            if let Err(why) = command
+                .create_response(&ctx.http, CreateInteractionResponse::Message(data))
+                .await
+            {
+                error!("Cannot respond to slash command: {}", why);
+            }
+        }
+        "review" => {
+            let (submission, pending) = bot.submissions.oldest_pending().await;
+            let Some(submission) = submission else {
+                respond_ephemeral(&ctx, &command, t(locale, "submission-none-pending")).await;
+                return;
+            };
+
+            let (embed, buttons) = review_message(&submission, locale);
+            let data = CreateInteractionResponseMessage::new()
+                .content(t_args(
+                    locale,
+                    "submission-pending-count",
+                    &fluent_args!["count" => pending],
+                ))
+                .embed(embed)
+                .components(buttons)
+                .ephemeral(true);
+            if let Err(why) = command
+                .create_response(&ctx.http, CreateInteractionResponse::Message(data))
+                .await
+            {
+                error!("Cannot respond to slash command: {}", why);
+            }
+        }
+        "allow" => {
+            let channel = sub_options.iter().find_map(|opt| match opt.value {
+                ResolvedValue::Channel(channel) if opt.name == "channel" => Some(channel.id),
+                _ => None,
+            });
+            let enabled = sub_options.iter().find_map(|opt| match opt.value {
+                ResolvedValue::Boolean(enabled) if opt.name == "enabled" => Some(enabled),
+                _ => None,
+            });
+            let (Some(channel), Some(enabled)) = (channel, enabled) else {
+                return;
+            };
+
+            let mut channel_config = bot.channel_config.lock().await;
+            if enabled {
+                channel_config.allowed.insert(channel);
+            } else {
+                channel_config.allowed.remove(&channel);
+            }
+            bot.save_channel_config(&channel_config);
+
+            let key = if enabled {
+                "admin-allow-added"
+            } else {
+                "admin-allow-removed"
+            };
+            let response_content = t_args(
+                locale,
+                key,
+                &fluent_args![
+                    "channel" => channel.mention().to_string(),
+                    "count" => channel_config.allowed.len()
+                ],
+            );
+            respond_ephemeral(&ctx, &command, response_content).await;
+        }
+        "config" => {
+            let channel = sub_options
+                .iter()
+                .find_map(|opt| match opt.value {
+                    ResolvedValue::Channel(channel) => Some(channel.id),
+                    _ => None,
+                })
+                .unwrap_or(command.channel_id);
+
+            let mut channel_config = bot.channel_config.lock().await;
+            let settings = channel_config.settings.entry(channel).or_default();
+            for opt in sub_options {
+                match (opt.name, &opt.value) {
+                    ("difficulty", ResolvedValue::String(value)) => {
+                        settings.difficulty = Difficulty::parse(value).unwrap_or_default();
+                    }
+                    ("theme", ResolvedValue::String(value)) => {
+                        settings.theme = Some(value.trim().to_string()).filter(|v| !v.is_empty());
+                    }
+                    ("language", ResolvedValue::String(value)) => {
+                        settings.locale = Locale::from_discord(value);
+                    }
+                    ("timer", ResolvedValue::Integer(minutes)) => {
+                        settings.time_limit = Some(*minutes as u64).filter(|m| *m > 0);
+                    }
+                    ("compact", ResolvedValue::Boolean(compact)) => {
+                        settings.display = if *compact {
+                            DisplayMode::Compact
+                        } else {
+                            DisplayMode::Verbose
+                        };
+                    }
+                    ("participants", ResolvedValue::Boolean(participants_only)) => {
+                        settings.participants_only = *participants_only;
+                    }
+                    ("turns", ResolvedValue::Boolean(turn_based)) => {
+                        settings.turn_based = *turn_based;
+                    }
+                    ("library", ResolvedValue::Boolean(reuse_library)) => {
+                        settings.reuse_library = *reuse_library;
+                    }
+                    _ => {}
+                }
+            }
+            let embed = settings_embed(channel, settings, locale);
+            bot.save_channel_config(&channel_config);
+
+            let data = CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .ephemeral(true);
+            if let Err(why) = command
+                .create_response(&ctx.http, CreateInteractionResponse::Message(data))
+                .await
+            {
+                error!("Cannot respond to slash command: {}", why);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn settings_embed(channel: ChannelId, settings: &ChannelSettings, locale: Locale) -> CreateEmbed {
+    let none = t(locale, "admin-config-none");
+    let language = match settings.locale {
+        Some(language) => t(language, "language-name"),
+        None => t(locale, "admin-config-auto"),
+    };
+    let time_limit = match settings.time_limit {
+        Some(minutes) => t_args(
+            locale,
+            "admin-config-minutes",
+            &fluent_args!["minutes" => minutes],
+        ),
+        None => none.clone(),
+    };
+    let yes_no = |value: bool| {
+        t(
+            locale,
+            if value {
+                "admin-config-on"
+            } else {
+                "admin-config-off"
+            },
+        )
+    };
+
+    CreateEmbed::new()
+        .color(0x808080)
+        .title(t(locale, "admin-config-title"))
+        .description(channel.mention().to_string())
+        .field(
+            t(locale, "admin-config-difficulty"),
+            t(
+                locale,
+                &format!(
+                    "choice-admin-config-difficulty-{}",
+                    settings.difficulty.as_str()
+                ),
+            ),
+            true,
+        )
+        .field(
+            t(locale, "admin-config-theme"),
+            settings.theme.clone().unwrap_or(none),
+            true,
+        )
+        .field(t(locale, "admin-config-language"), language, true)
+        .field(t(locale, "admin-config-timer"), time_limit, true)
+        .field(
+            t(locale, "admin-config-compact"),
+            yes_no(settings.display == DisplayMode::Compact),
+            true,
+        )
+        .field(
+            t(locale, "admin-config-participants"),
+            yes_no(settings.participants_only),
+            true,
+        )
+        .field(
+            t(locale, "admin-config-turns"),
+            yes_no(settings.turn_based),
+            true,
+        )
+        .field(
+            t(locale, "admin-config-library"),
+            yes_no(settings.reuse_library),
+            true,
+        )
+}
+
+async fn status_embed(bot: &Bot, locale: Locale) -> CreateEmbed {
+    let sessions: Vec<_> = bot.sessions.lock().await.values().cloned().collect();
+    let mut playing = 0;
+    for session in &sessions {
+        if !matches!(
+            session.lock().await.game.state(),
+            State::Idle | State::Lobby
+        ) {
+            playing += 1;
+        }
+    }
+
+    let channel_config = bot.channel_config.lock().await;
+    let allowed = if channel_config.allowed.is_empty() {
+        t(locale, "admin-status-all-channels")
+    } else {
+        channel_config
+            .allowed
+            .iter()
+            .map(|channel| channel.mention().to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let uptime = bot.started_at.elapsed().as_secs();
+    let uptime = format!(
+        "{}:{:02}:{:02}",
+        uptime / 3600,
+        uptime / 60 % 60,
+        uptime % 60
+    );
+
+    CreateEmbed::new()
+        .color(0x808080)
+        .title(t(locale, "admin-status-title"))
+        .field(t(locale, "admin-status-uptime"), uptime, true)
+        .field(
+            t(locale, "admin-status-sessions"),
+            t_args(
+                locale,
+                "admin-status-sessions-value",
+                &fluent_args!["playing" => playing, "total" => sessions.len()],
+            ),
+            true,
+        )
+        .field(t(locale, "admin-status-channels"), allowed, false)
+}
+
+// 最近のモデレーションの記録
+async fn modlog_embed(bot: &Bot, locale: Locale) -> CreateEmbed {
+    let log = bot.moderation.log.lock().await;
+    let description = if log.is_empty() {
+        t(locale, "admin-modlog-empty")
+    } else {
+        log.iter()
+            .rev()
+            .take(MODLOG_LIMIT)
+            .map(|record| {
+                let user = record
+                    .user_id
+                    .map(|user| user.mention().to_string())
+                    .unwrap_or_else(|| "Bot".to_string());
+                let text: String = record.text.chars().take(80).collect();
+                format!(
+                    "<t:{}:f> {} {} `{:?}` `{:?}` {}\n> {}",
+                    record.timestamp,
+                    record.channel_id.mention(),
+                    user,
+                    record.target,
+                    record.action,
+                    record.categories.join(", "),
+                    text.replace('\n', " ")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    CreateEmbed::new()
+        .color(0x808080)
+        .title(t(locale, "admin-modlog-title"))
+        .description(description)
+}
+
+async fn respond_ephemeral(ctx: &Context, command: &CommandInteraction, response_content: String) {
+    let data = CreateInteractionResponseMessage::new()
+        .content(response_content)
+        .ephemeral(true);
+    let builder = CreateInteractionResponse::Message(data);
+
+    if let Err(why) = command.create_response(&ctx.http, builder).await {
+        error!("Cannot respond to slash command: {}", why);
+    }
+}
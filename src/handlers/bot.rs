@@ -1,55 +1,346 @@
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, RwLock};
 
-use serenity::all::{GuildId, Interaction, UserId};
+use chrono::{FixedOffset, NaiveDate, Utc};
+use serenity::all::{
+    ChannelId, Command, CommandOptionType, GuildId, Interaction, InteractionContext, Member,
+    RoleId, User, UserId,
+};
 use serenity::async_trait;
-use serenity::builder::{CreateCommand, CreateCommandOption};
 
 use std::collections::HashMap;
-use tracing::info;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{error, info};
 
 use crate::api::OpenAIClient;
-use crate::models::ChatCompletionMessage;
+use crate::config::Config;
+use crate::constants::prompt::load_system_prompts;
+use crate::critique::Critic;
+use crate::daily::{Daily, DailyPuzzle};
+use crate::leaderboard::Leaderboard;
+use crate::library::Library;
+use crate::moderation::{Moderation, Moderator};
+use crate::pause::PausedGames;
+use crate::scoring::{Award, ScoringRules};
+use crate::storage::Store;
+use crate::submissions::Submissions;
+use crate::transcript::Transcripts;
 
 use serenity::model::channel::Message;
 use serenity::model::gateway::Ready;
 use serenity::prelude::*;
 
-use crate::constants::prompt::SYSTEM_PROMPT;
-use crate::handlers::{handle_command, handle_component, handle_message};
-use crate::models::{Role, State};
+use crate::handlers::{handle_command, handle_component, handle_message, handle_modal};
+use crate::i18n::{self, Locale};
+use crate::models::{ChannelConfig, ChannelSettings, Session, SoloKind, SoloStats};
+use crate::utils::question_generator::Generator;
+use crate::utils::scheduler::{start_daily_puzzle, start_weekly_leaderboard};
+
+// タイマーなど、イベントの外で動く処理から Bot を使うためのキー
+// クライアントのデータに登録しておき、Context から取り出す
+pub struct BotKey;
+
+impl TypeMapKey for BotKey {
+    type Value = Arc<Bot>;
+}
+
+// デイリーパズルの問題の日付と、そのセッション
+type DailySession = (NaiveDate, Arc<Mutex<Session>>);
 
 pub struct Bot {
     pub discord_guild_id: GuildId,
-    pub join_users: Mutex<Vec<UserId>>,
     pub openai_client: OpenAIClient,
-    pub scores: Mutex<HashMap<String, u32>>,
-    pub messages: Mutex<Vec<ChatCompletionMessage>>,
-    pub system_prompt: ChatCompletionMessage,
-    pub state: Mutex<State>,
+    pub sessions: Mutex<HashMap<ChannelId, Arc<Mutex<Session>>>>,
+    // ソロモードのユーザーごとのセッション
+    pub solo_sessions: Mutex<HashMap<UserId, Arc<Mutex<Session>>>>,
+    pub solo_stats: Mutex<HashMap<UserId, SoloStats>>,
+    // デイリーパズルのユーザーごとのセッションと、その問題の日付
+    pub daily_sessions: Mutex<HashMap<UserId, DailySession>>,
+    pub answer_prefixes: Vec<String>,
+    pub moderator_role_id: Option<RoleId>,
+    pub prompt_dir: Option<String>,
+    pub system_prompts: Arc<RwLock<HashMap<Locale, String>>>,
+    pub store: Store,
+    pub channel_config: Mutex<ChannelConfig>,
+    pub moderation: Arc<Moderation>,
+    pub leaderboard: Arc<Leaderboard>,
+    pub library: Arc<Library>,
+    pub critic: Arc<Critic>,
+    pub puzzle_attempts: usize,
+    pub scoring: RwLock<ScoringRules>,
+    pub leaderboard_channel_id: Option<ChannelId>,
+    pub daily: Arc<Daily>,
+    pub daily_channel_id: Option<ChannelId>,
+    pub submissions: Submissions,
+    pub review_channel_id: Option<ChannelId>,
+    pub transcripts: Arc<Transcripts>,
+    pub paused_games: PausedGames,
+    // 書き出すトランスクリプトの時刻の時差
+    pub timezone: FixedOffset,
+    // 再接続でreadyが何度呼ばれても定期投稿を重複して開始しないようにする
+    scheduler_started: AtomicBool,
+    pub started_at: Instant,
 }
 
 impl Bot {
-    pub fn new(discord_guild_id: GuildId, openai_api_key: String) -> Self {
-        let system_prompt = ChatCompletionMessage::new(Role::System, SYSTEM_PROMPT.to_string());
+    pub fn new(config: &Config) -> Self {
+        let store = Store::new(&config.data_dir);
+        // 一時停止していたゲームは再起動後も /resume で続けられるようにする
+        let mut paused_games = PausedGames::new(store.clone());
+        let sessions = paused_games.sessions();
 
         Self {
-            discord_guild_id,
-            join_users: Mutex::new(vec![]),
-            openai_client: OpenAIClient::new(openai_api_key),
-            scores: Mutex::new(HashMap::new()),
-            messages: Mutex::new(vec![system_prompt.clone()]),
-            system_prompt,
-            state: Mutex::new(State::Idle),
+            discord_guild_id: config.discord_guild_id,
+            openai_client: OpenAIClient::new(config.openai_api_key.clone())
+                .with_endpoint(config.openai_base_url.clone(), config.openai_model.clone()),
+            sessions: Mutex::new(sessions),
+            solo_sessions: Mutex::new(HashMap::new()),
+            solo_stats: Mutex::new(store.load("solo_stats")),
+            daily_sessions: Mutex::new(HashMap::new()),
+            answer_prefixes: config.answer_prefixes.clone(),
+            moderator_role_id: config.moderator_role_id,
+            prompt_dir: config.prompt_dir.clone(),
+            system_prompts: Arc::new(RwLock::new(load_system_prompts(
+                config.prompt_dir.as_deref(),
+            ))),
+            channel_config: Mutex::new(store.load("channels")),
+            moderation: Arc::new(Moderation::new(
+                Moderator::from_config(&config.moderation, &config.moderation_words),
+                config.moderation_action,
+                config.puzzle_moderation_action,
+                store.clone(),
+            )),
+            leaderboard: Arc::new(Leaderboard::new(store.clone(), config.timezone)),
+            library: Arc::new(Library::new(store.clone())),
+            critic: Arc::new(Critic::new(store.clone())),
+            puzzle_attempts: config.puzzle_attempts,
+            scoring: RwLock::new(store.load("scoring")),
+            leaderboard_channel_id: config.leaderboard_channel_id,
+            daily: Arc::new(Daily::new(
+                store.clone(),
+                config.timezone,
+                config.daily_hour,
+            )),
+            daily_channel_id: config.daily_channel_id,
+            submissions: Submissions::new(store.clone()),
+            review_channel_id: config.review_channel_id,
+            transcripts: Arc::new(Transcripts::new(store.clone())),
+            paused_games,
+            timezone: config.timezone,
+            scheduler_started: AtomicBool::new(false),
+            store,
+            started_at: Instant::now(),
         }
     }
 
-    pub async fn reset_scores(&self) {
-        self.scores.lock().await.clear();
+    pub async fn system_prompt(&self, locale: Locale) -> String {
+        self.system_prompts.read().await[&locale].clone()
+    }
+
+    // プロンプトのファイルを読み込み直す。次の出題から反映される
+    pub async fn reload_prompts(&self) {
+        *self.system_prompts.write().await = load_system_prompts(self.prompt_dir.as_deref());
     }
 
-    pub async fn reset_messages(&self) {
-        self.messages.lock().await.clear();
-        self.messages.lock().await.push(self.system_prompt.clone());
+    // 得点のルールを読み込み直す
+    pub async fn reload_scoring(&self) {
+        *self.scoring.write().await = self.store.load("scoring");
+    }
+
+    // 正解した時点の参加者ごとの得点
+    pub async fn score(&self, session: &Session, solver: UserId) -> Vec<Award> {
+        self.scoring
+            .read()
+            .await
+            .score(solver, session, Utc::now().timestamp())
+    }
+
+    // サーバーの管理権限を持つユーザーか、設定されたモデレーターロールを持つユーザーかどうか
+    pub fn is_moderator(&self, member: Option<&Member>) -> bool {
+        let Some(member) = member else {
+            return false;
+        };
+
+        let permissions = member.permissions.unwrap_or_default();
+        permissions.administrator()
+            || permissions.manage_guild()
+            || self
+                .moderator_role_id
+                .is_some_and(|role| member.roles.contains(&role))
+    }
+
+    // スレッドの場合は親チャンネルが許可されているかで判断する
+    pub async fn is_channel_allowed(
+        &self,
+        channel_id: ChannelId,
+        parent: Option<ChannelId>,
+    ) -> bool {
+        let channel_config = self.channel_config.lock().await;
+        channel_config.allowed.is_empty()
+            || channel_config.allowed.contains(&channel_id)
+            || parent.is_some_and(|parent| channel_config.allowed.contains(&parent))
+    }
+
+    pub async fn channel_settings(&self, channel_id: ChannelId) -> ChannelSettings {
+        self.channel_config
+            .lock()
+            .await
+            .settings
+            .get(&channel_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn save_channel_config(&self, channel_config: &ChannelConfig) {
+        if let Err(e) = self.store.save("channels", channel_config) {
+            error!("チャンネル設定の保存に失敗しました: {:?}", e);
+        }
+    }
+
+    // チャンネルのセッションを取得する。存在しない場合はチャンネルの設定で作成する
+    pub async fn session(&self, channel_id: ChannelId) -> Arc<Mutex<Session>> {
+        if let Some(session) = self.find_session(channel_id).await {
+            return session;
+        }
+
+        let settings = self.channel_settings(channel_id).await;
+        self.sessions
+            .lock()
+            .await
+            .entry(channel_id)
+            .or_insert_with(|| Arc::new(Mutex::new(Session::new(settings))))
+            .clone()
+    }
+
+    // チャンネルのセッションがあれば取得する
+    pub async fn find_session(&self, channel_id: ChannelId) -> Option<Arc<Mutex<Session>>> {
+        self.sessions.lock().await.get(&channel_id).cloned()
+    }
+
+    pub async fn insert_session(
+        &self,
+        channel_id: ChannelId,
+        session: Session,
+    ) -> Arc<Mutex<Session>> {
+        let session = Arc::new(Mutex::new(session));
+        self.sessions
+            .lock()
+            .await
+            .insert(channel_id, session.clone());
+        session
+    }
+
+    pub async fn remove_session(&self, channel_id: ChannelId) {
+        self.sessions.lock().await.remove(&channel_id);
+    }
+
+    pub async fn solo_session(&self, user_id: UserId) -> Arc<Mutex<Session>> {
+        self.solo_sessions
+            .lock()
+            .await
+            .entry(user_id)
+            .or_insert_with(|| {
+                Arc::new(Mutex::new(Session {
+                    solo: Some(SoloKind::Practice),
+                    ..Session::new(ChannelSettings::default())
+                }))
+            })
+            .clone()
+    }
+
+    // デイリーパズルのセッション。日付が変わっていたら新しい問題のセッションにする
+    pub async fn daily_session(
+        &self,
+        user_id: UserId,
+        puzzle: &DailyPuzzle,
+    ) -> Arc<Mutex<Session>> {
+        let mut daily_sessions = self.daily_sessions.lock().await;
+        daily_sessions.retain(|_, (date, _)| *date == puzzle.date);
+        daily_sessions
+            .entry(user_id)
+            .or_insert_with(|| {
+                let mut session = Session {
+                    solo: Some(SoloKind::Daily(puzzle.date)),
+                    ..Session::new(ChannelSettings {
+                        locale: Some(puzzle.locale),
+                        ..Default::default()
+                    })
+                };
+                let _ = session.game.generate();
+                session.game.problem = puzzle.problem.clone();
+                session.game.solution = puzzle.solution.clone();
+                session.game.puzzle_started_at = Utc::now().timestamp();
+                let _ = session.game.start();
+                (puzzle.date, Arc::new(Mutex::new(session)))
+            })
+            .1
+            .clone()
+    }
+
+    pub fn generator(&self) -> Generator {
+        Generator {
+            client: self.openai_client.clone(),
+            moderation: self.moderation.clone(),
+            library: self.library.clone(),
+            critic: self.critic.clone(),
+            attempts: self.puzzle_attempts,
+        }
+    }
+
+    pub async fn solo_stats(&self, user_id: UserId) -> SoloStats {
+        self.solo_stats
+            .lock()
+            .await
+            .get(&user_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub async fn update_solo_stats(&self, user_id: UserId, update: impl FnOnce(&mut SoloStats)) {
+        let mut solo_stats = self.solo_stats.lock().await;
+        update(solo_stats.entry(user_id).or_default());
+        if let Err(e) = self.store.save("solo_stats", &*solo_stats) {
+            error!("ソロモードの成績の保存に失敗しました: {:?}", e);
+        }
+    }
+
+    // 出題を始めた問題を記録する。問題を表示できてから呼ぶ
+    // ライブラリの問題はこのチャンネルで出題済みにし、DMでの出題は個人の成績の挑戦数に数える
+    pub async fn record_start(&self, channel_id: ChannelId, session: &Session) {
+        if let Some(id) = session.game.puzzle_id.filter(|_| !session.game.unlisted) {
+            self.library.mark_played(id, channel_id).await;
+        }
+        if let Some(user_id) = session.dm_user {
+            self.update_solo_stats(user_id, |stats| stats.played += 1)
+                .await;
+        }
+    }
+
+    // 正解をランキング用に記録する
+    // DM・ソロモードでの正解はサーバーのランキングとは分けて、個人の成績に記録する
+    pub async fn record_solve(&self, channel_id: ChannelId, user: &User, session: &Session) {
+        if session.dm_user.is_some() || session.solo == Some(SoloKind::Practice) {
+            let questions = session.game.qa_log.len() as u32;
+            let hints = session.game.hints.len() as u32;
+            self.update_solo_stats(user.id, |stats| stats.record_solve(questions, hints))
+                .await;
+            return;
+        }
+
+        let user_name = user
+            .global_name
+            .clone()
+            .unwrap_or_else(|| user.name.clone());
+        self.leaderboard
+            .record(
+                channel_id,
+                session.parent_channel,
+                user.id,
+                user_name,
+                session.game.qa_log.len(),
+            )
+            .await;
     }
 }
 
@@ -58,32 +349,237 @@ impl EventHandler for Bot {
     async fn ready(&self, ctx: Context, ready: Ready) {
         info!("{} is connected!", ready.user.name);
 
-        let commands = vec![
-            CreateCommand::new("play").description("ゲームスタート"), // ゲームスタート
-            CreateCommand::new("join").description("参加"),           // 参加
-            CreateCommand::new("question")
-                .description("質問を送信します")
+        // ゲームの進行に使うコマンドはDMでも使えるようにグローバルに登録する
+        let dm_commands: Vec<_> = vec![
+            i18n::command("play").add_option(["none", "public", "private"].iter().fold(
+                i18n::option(CommandOptionType::String, "play", "thread"),
+                |option, value| i18n::string_choice(option, "play", "thread", value),
+            )), // ゲームスタート
+            i18n::command("question").add_option(
+                i18n::option(CommandOptionType::String, "question", "q")
+                    .max_length(100)
+                    .required(true),
+            ),
+            i18n::command("answer").add_option(
+                i18n::option(CommandOptionType::String, "answer", "a")
+                    .max_length(100)
+                    .required(true),
+            ),
+            i18n::command("giveup"),
+            i18n::command("hint"),
+            i18n::command("pause"),
+            i18n::command("resume"),
+            i18n::command("solo")
+                .add_option(
+                    i18n::option(CommandOptionType::SubCommand, "solo", "start").add_sub_option(
+                        ["easy", "normal", "hard"].iter().fold(
+                            i18n::option(CommandOptionType::String, "solo-start", "difficulty"),
+                            |option, value| {
+                                i18n::string_choice(option, "solo-start", "difficulty", value)
+                            },
+                        ),
+                    ),
+                )
+                .add_option(
+                    i18n::option(CommandOptionType::SubCommand, "solo", "question").add_sub_option(
+                        i18n::option(CommandOptionType::String, "solo-question", "q")
+                            .max_length(100)
+                            .required(true),
+                    ),
+                )
                 .add_option(
-                    CreateCommandOption::new(
-                        serenity::all::CommandOptionType::String,
-                        "q",
-                        "質問の内容を入力してください",
+                    i18n::option(CommandOptionType::SubCommand, "solo", "answer").add_sub_option(
+                        i18n::option(CommandOptionType::String, "solo-answer", "a")
+                            .max_length(100)
+                            .required(true),
+                    ),
+                )
+                .add_option(i18n::option(CommandOptionType::SubCommand, "solo", "hint"))
+                .add_option(i18n::option(
+                    CommandOptionType::SubCommand,
+                    "solo",
+                    "giveup",
+                ))
+                .add_option(i18n::option(CommandOptionType::SubCommand, "solo", "stats")),
+            i18n::command("display").add_option(
+                ["verbose", "compact"]
+                    .iter()
+                    .fold(
+                        i18n::option(CommandOptionType::String, "display", "mode"),
+                        |option, value| i18n::string_choice(option, "display", "mode", value),
                     )
-                    .max_length(100)
                     .required(true),
+            ),
+            i18n::command("chat").add_option(
+                i18n::option(CommandOptionType::Boolean, "chat", "enabled").required(true),
+            ),
+            i18n::command("export")
+                .add_option(["markdown", "json"].iter().fold(
+                    i18n::option(CommandOptionType::String, "export", "format"),
+                    |option, value| i18n::string_choice(option, "export", "format", value),
+                ))
+                .add_option(
+                    i18n::option(CommandOptionType::Integer, "export", "game").min_int_value(1),
                 ),
-            CreateCommand::new("answer")
-                .description("回答を送信します")
+            i18n::command("import")
+                .add_option(
+                    i18n::option(CommandOptionType::Attachment, "import", "file").required(true),
+                )
+                .add_option(i18n::option(CommandOptionType::Boolean, "import", "play")),
+        ]
+        .into_iter()
+        .map(|command| command.contexts(vec![InteractionContext::Guild, InteractionContext::BotDm]))
+        .collect();
+
+        let commands = vec![
+            i18n::command("join"), // 参加
+            i18n::command("team")
                 .add_option(
-                    CreateCommandOption::new(
-                        serenity::all::CommandOptionType::String,
-                        "a",
-                        "回答の内容を入力してください",
+                    i18n::option(CommandOptionType::SubCommand, "team", "create").add_sub_option(
+                        i18n::option(CommandOptionType::String, "team-create", "name")
+                            .max_length(30)
+                            .required(true),
+                    ),
+                )
+                .add_option(
+                    i18n::option(CommandOptionType::SubCommand, "team", "join").add_sub_option(
+                        i18n::option(CommandOptionType::String, "team-join", "name")
+                            .max_length(30)
+                            .required(true),
+                    ),
+                )
+                .add_option(i18n::option(CommandOptionType::SubCommand, "team", "list"))
+                .add_option(i18n::option(CommandOptionType::SubCommand, "team", "play")),
+            i18n::command("daily")
+                .add_option(
+                    i18n::option(CommandOptionType::SubCommand, "daily", "question")
+                        .add_sub_option(
+                            i18n::option(CommandOptionType::String, "daily-question", "q")
+                                .max_length(100)
+                                .required(true),
+                        ),
+                )
+                .add_option(
+                    i18n::option(CommandOptionType::SubCommand, "daily", "answer").add_sub_option(
+                        i18n::option(CommandOptionType::String, "daily-answer", "a")
+                            .max_length(100)
+                            .required(true),
+                    ),
+                )
+                .add_option(i18n::option(CommandOptionType::SubCommand, "daily", "hint"))
+                .add_option(i18n::option(
+                    CommandOptionType::SubCommand,
+                    "daily",
+                    "giveup",
+                ))
+                .add_option(i18n::option(
+                    CommandOptionType::SubCommand,
+                    "daily",
+                    "board",
+                )),
+            i18n::command("library"),
+            i18n::command("submit").add_option(
+                ["easy", "normal", "hard"]
+                    .iter()
+                    .fold(
+                        i18n::option(CommandOptionType::String, "submit", "difficulty"),
+                        |option, value| i18n::string_choice(option, "submit", "difficulty", value),
                     )
-                    .max_length(100)
                     .required(true),
+            ),
+            i18n::command("leaderboard").add_option(
+                ["session", "week", "month", "all"].iter().fold(
+                    i18n::option(CommandOptionType::String, "leaderboard", "period"),
+                    |option, value| i18n::string_choice(option, "leaderboard", "period", value),
+                ),
+            ),
+            i18n::command("admin")
+                .add_option(i18n::option(
+                    CommandOptionType::SubCommand,
+                    "admin",
+                    "reset",
+                ))
+                .add_option(i18n::option(CommandOptionType::SubCommand, "admin", "end"))
+                .add_option(i18n::option(
+                    CommandOptionType::SubCommand,
+                    "admin",
+                    "reload",
+                ))
+                .add_option(i18n::option(
+                    CommandOptionType::SubCommand,
+                    "admin",
+                    "status",
+                ))
+                .add_option(i18n::option(
+                    CommandOptionType::SubCommand,
+                    "admin",
+                    "modlog",
+                ))
+                .add_option(i18n::option(
+                    CommandOptionType::SubCommand,
+                    "admin",
+                    "review",
+                ))
+                .add_option(
+                    i18n::option(CommandOptionType::SubCommand, "admin", "allow")
+                        .add_sub_option(
+                            i18n::option(CommandOptionType::Channel, "admin-allow", "channel")
+                                .required(true),
+                        )
+                        .add_sub_option(
+                            i18n::option(CommandOptionType::Boolean, "admin-allow", "enabled")
+                                .required(true),
+                        ),
+                )
+                .add_option(
+                    i18n::option(CommandOptionType::SubCommand, "admin", "config")
+                        .add_sub_option(i18n::option(
+                            CommandOptionType::Channel,
+                            "admin-config",
+                            "channel",
+                        ))
+                        .add_sub_option(["easy", "normal", "hard"].iter().fold(
+                            i18n::option(CommandOptionType::String, "admin-config", "difficulty"),
+                            |option, value| {
+                                i18n::string_choice(option, "admin-config", "difficulty", value)
+                            },
+                        ))
+                        .add_sub_option(
+                            i18n::option(CommandOptionType::String, "admin-config", "theme")
+                                .max_length(50),
+                        )
+                        .add_sub_option(["auto", "ja", "en"].iter().fold(
+                            i18n::option(CommandOptionType::String, "admin-config", "language"),
+                            |option, value| {
+                                i18n::string_choice(option, "admin-config", "language", value)
+                            },
+                        ))
+                        .add_sub_option(
+                            i18n::option(CommandOptionType::Integer, "admin-config", "timer")
+                                .min_int_value(0)
+                                .max_int_value(120),
+                        )
+                        .add_sub_option(i18n::option(
+                            CommandOptionType::Boolean,
+                            "admin-config",
+                            "compact",
+                        ))
+                        .add_sub_option(i18n::option(
+                            CommandOptionType::Boolean,
+                            "admin-config",
+                            "participants",
+                        ))
+                        .add_sub_option(i18n::option(
+                            CommandOptionType::Boolean,
+                            "admin-config",
+                            "turns",
+                        ))
+                        .add_sub_option(i18n::option(
+                            CommandOptionType::Boolean,
+                            "admin-config",
+                            "library",
+                        )),
                 ),
-            CreateCommand::new("giveup").description("ゲームを終了します"),
         ];
 
         let commands = &self
@@ -93,17 +589,52 @@ impl EventHandler for Bot {
             .unwrap();
 
         info!("Registered commands: {:#?}", commands);
+
+        let dm_commands = Command::set_global_commands(&ctx.http, dm_commands)
+            .await
+            .unwrap();
+
+        info!("Registered global commands: {:#?}", dm_commands);
+
+        if self.scheduler_started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        if let Some(channel_id) = self.leaderboard_channel_id {
+            let locale = self
+                .channel_settings(channel_id)
+                .await
+                .locale
+                .unwrap_or_default();
+            start_weekly_leaderboard(
+                ctx.http.clone(),
+                self.leaderboard.clone(),
+                channel_id,
+                locale,
+            );
+        }
+        if let Some(channel_id) = self.daily_channel_id {
+            // 難易度・テーマ・言語はデイリーパズルのチャンネルの設定に従う
+            start_daily_puzzle(
+                ctx.http.clone(),
+                self.daily.clone(),
+                self.generator(),
+                self.system_prompts.clone(),
+                channel_id,
+                self.channel_settings(channel_id).await,
+            );
+        }
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
         match interaction {
             Interaction::Command(command) => handle_command(ctx, command, self).await,
             Interaction::Component(component) => handle_component(ctx, component, self).await,
+            Interaction::Modal(modal) => handle_modal(ctx, modal, self).await,
             _ => (),
         }
     }
 
     async fn message(&self, ctx: Context, msg: Message) {
-        handle_message(ctx, msg).await;
+        handle_message(ctx, msg, self).await;
     }
 }
@@ -1,266 +1,579 @@
-use crate::models::{ChatCompletionMessage, Role, State};
-use serenity::all::CommandInteraction;
+use crate::constants::prompt::game_prompt;
+use crate::i18n::{t, t_args, Locale};
+use crate::models::{DisplayMode, Session};
+use fluent::fluent_args;
+use serenity::all::{ChannelType, CommandInteraction};
 use serenity::builder::{
-    CreateActionRow, CreateButton, CreateEmbed, CreateInteractionResponse,
-    CreateInteractionResponseMessage,
+    CreateInteractionResponse, CreateInteractionResponseFollowup, CreateInteractionResponseMessage,
+    CreateThread, EditInteractionResponse,
 };
-use serenity::model::application::ButtonStyle;
-use serenity::model::user::User;
 use serenity::prelude::*;
+use tracing::error;
 
-use crate::handlers::Bot;
-use crate::utils::question_generator::generate_question_builder;
+use crate::game::{Event, Player, State};
+use crate::handlers::{
+    handle_admin, handle_daily, handle_export, handle_import, handle_solo, handle_submit,
+    handle_team, Bot,
+};
+use crate::leaderboard::Period;
+use crate::moderation::{ModerationAction, Target};
+use crate::utils::finish_game::{finish_game, Finish, Reply};
+use crate::utils::leaderboard_message::leaderboard_message;
+use crate::utils::library_message::library_embed;
+use crate::utils::qa_board::{short_hint, update_board};
+use crate::utils::question_generator::generate_question;
+use crate::utils::question_message::{question_message, question_response};
+use crate::utils::timer::{pause_timer, resume_timer, start_timer};
+use crate::utils::turn::{advance_turn, start_turns, turn_notice};
+
+// /library で表示する問題の数
+const LIBRARY_LIMIT: usize = 10;
 
 pub async fn handle_command(ctx: Context, command: CommandInteraction, bot: &Bot) {
+    if command.data.name == "admin" {
+        handle_admin(ctx, command, bot).await;
+        return;
+    }
+    // ソロモードとデイリーパズルは本人にだけ表示されるため、許可チャンネル以外でも遊べる
+    if command.data.name == "solo" {
+        handle_solo(ctx, command, bot).await;
+        return;
+    }
+    if command.data.name == "daily" {
+        handle_daily(ctx, command, bot).await;
+        return;
+    }
+    if command.data.name == "submit" {
+        handle_submit(ctx, command, bot).await;
+        return;
+    }
+
+    let locale = Locale::from_interaction(&command.locale, command.guild_locale.as_deref());
+    // DMではユーザーごとにチャンネルが分かれるため、そのままユーザー専用のセッションになる
+    let in_dm = command.guild_id.is_none();
+    let parent_channel = if is_thread(&command) {
+        command
+            .channel
+            .as_ref()
+            .and_then(|channel| channel.parent_id)
+    } else {
+        None
+    };
+    if !in_dm
+        && !bot
+            .is_channel_allowed(command.channel_id, parent_channel)
+            .await
+    {
+        respond_to_command_ephemeral(&ctx, &command, t(locale, "channel-not-allowed")).await;
+        return;
+    }
+    if command.data.name == "export" {
+        handle_export(ctx, command, bot).await;
+        return;
+    }
+    if command.data.name == "import" {
+        handle_import(ctx, command, bot).await;
+        return;
+    }
+
+    let session = bot.session(command.channel_id).await;
+    let mut session = session.lock().await;
+    if in_dm {
+        session.dm_user = Some(command.user.id);
+    }
+    // 一時停止中は質問・回答を受け付けない
+    if session.game.state() == State::Paused
+        && matches!(
+            command.data.name.as_str(),
+            "question" | "answer" | "hint" | "giveup"
+        )
+    {
+        respond_to_command_ephemeral(&ctx, &command, t(locale, "game-paused-notice")).await;
+        return;
+    }
+
     match command.data.name.as_str() {
         "join" => {
             let user_id = command.user.id;
             let user_name = command.user.name.clone();
-            let mut join_users = bot.join_users.lock().await;
-            join_users.push(user_id);
-            let response_content = format!(
-              "{} さん(ID: {})が参加しました。\n現在の参加者数は{}人です。\nゲームを開始するには\\startを入力してください",
-              user_name,
-              user_id,
-              join_users.len()
-          );
+            if !session.join_users.contains(&user_id) {
+                session.join_users.push(user_id);
+            }
+            // 開始前なら参加者の募集中にする。ゲーム中に参加した場合は状態を変えない
+            let _ = session.game.join();
+            let response_content = t_args(
+                locale,
+                "joined",
+                &fluent_args![
+                    "name" => user_name,
+                    "id" => user_id.to_string(),
+                    "count" => session.join_users.len()
+                ],
+            );
 
             respond_to_command(&ctx, &command, response_content).await;
         }
         "play" => {
-            let mut state = bot.state.lock().await;
-            if !matches!(*state, State::Idle) {
-                respond_to_command_ephemeral(
-                    &ctx,
-                    &command,
-                    "実行するタイミングが正しくありません".to_string(),
-                )
-                .await;
+            let thread_kind = match string_option(&command, "thread") {
+                Some("public") => Some(ChannelType::PublicThread),
+                Some("private") => Some(ChannelType::PrivateThread),
+                _ => None,
+            };
+
+            if let Some(kind) = thread_kind {
+                if in_dm {
+                    respond_to_command_ephemeral(&ctx, &command, t(locale, "thread-dm")).await;
+                    return;
+                }
+                if session.parent_channel.is_some() || is_thread(&command) {
+                    respond_to_command_ephemeral(&ctx, &command, t(locale, "thread-nested")).await;
+                    return;
+                }
+                play_in_thread(&ctx, &command, bot, locale, kind).await;
                 return;
             }
-            let builder = generate_question_builder(bot).await;
-            if let Err(why) = command.create_response(&ctx.http, builder).await {
-                println!("Cannot respond to slash command: {}", why);
-                println!("command.data: {:?}", command.data);
+
+            if matches!(session.game.state(), State::Waiting) || session.game.generate().is_err() {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "invalid-timing")).await;
+                return;
+            }
+            // 批評や作り直しで問題の生成に時間がかかるため先に応答を保留する
+            if let Err(why) = command.defer(&ctx.http).await {
+                error!("Cannot defer slash command: {}", why);
+                session.game.rollback();
+                return;
+            }
+            session.settings = bot.channel_settings(command.channel_id).await;
+            let game_locale = session.settings.locale.unwrap_or(locale);
+
+            let question =
+                generate_question(bot, &mut session, game_locale, command.channel_id).await;
+            let builder = question_response(&question, game_locale);
+            if let Err(why) = command.edit_response(&ctx.http, builder).await {
+                error!("Cannot edit slash command response: {}", why);
+                session.game.rollback();
                 return;
             }
-            *state = State::Playing;
+            // 出題できなかった場合は元の状態に戻す
+            if question.is_err() {
+                session.game.rollback();
+                return;
+            }
+            let _ = session.game.start();
+            bot.record_start(command.channel_id, &session).await;
+            start_timer(&ctx, bot, command.channel_id, &mut session).await;
+            start_turns(&ctx, bot, command.channel_id, &session).await;
         }
         "question" => {
-            let state = bot.state.lock().await;
-            if !matches!(*state, State::Playing) {
-                respond_to_command_ephemeral(
-                    &ctx,
-                    &command,
-                    "実行するタイミングが正しくありません".to_string(),
-                )
-                .await;
+            if !session.game.is_playing() {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "invalid-timing")).await;
                 return;
             }
-            let argument = command
-                .data
-                .options
-                .iter()
-                .find(|opt| opt.name == "q")
-                .cloned();
-
-            let value = argument.unwrap().value;
-            let mut question = "質問です。".to_string();
-            question.push_str(value.as_str().unwrap());
-
-            bot.messages
-                .lock()
-                .await
-                .push(ChatCompletionMessage::new(Role::User, question));
-
-            let response = bot
-                .openai_client
-                .send_request(&bot.messages.lock().await)
+            if session.has_team_threads() {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "team-play-in-thread"))
+                    .await;
+                return;
+            }
+            if !session.can_play(command.user.id) {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "not-participant")).await;
+                return;
+            }
+            if session
+                .current_turn()
+                .is_some_and(|user| user != command.user.id)
+            {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "not-your-turn")).await;
+                return;
+            }
+            let value = string_option(&command, "q").unwrap_or_default();
+            let Some(warned) =
+                screen_input(&ctx, &command, bot, Target::Question, value, locale).await
+            else {
+                return;
+            };
+            let player = Player::from(&command.user);
+            let event = session
+                .game
+                .question(&bot.openai_client, &player, value)
                 .await;
 
-            if let Ok(res) = response {
-                bot.messages
-                    .lock()
-                    .await
-                    .push(ChatCompletionMessage::new(Role::Assistant, res.to_string()));
+            if let Ok(Event::Replied { verdict, reply }) = event {
+                advance_turn(&ctx, bot, command.channel_id, &mut session).await;
 
-                respond_to_command(&ctx, &command, res).await;
+                match session.display {
+                    DisplayMode::Verbose => {
+                        let response_content = match turn_notice(&session) {
+                            Some(notice) => format!("{}\n\n{}", reply, notice),
+                            None => reply,
+                        };
+                        respond_to_command(&ctx, &command, response_content).await;
+                    }
+                    DisplayMode::Compact => {
+                        let hint = short_hint(&reply, game_prompt(session.game.locale));
+                        let response_content = format!("{} {}", verdict.emoji(), hint);
+                        respond_to_command_ephemeral(&ctx, &command, response_content).await;
+                        update_board(&ctx, command.channel_id, &mut session).await;
+                    }
+                }
+                if warned {
+                    followup_ephemeral(&ctx, &command, t(locale, "moderation-warned")).await;
+                }
             } else {
-                respond_to_command(
-                    &ctx,
-                    &command,
-                    "APIの返却値取得においてエラーが発生しました".to_string(),
-                )
-                .await;
+                respond_to_command(&ctx, &command, t(locale, "api-error")).await;
             }
         }
         "answer" => {
-            let mut state = bot.state.lock().await;
-            if !matches!(*state, State::Playing) {
-                respond_to_command_ephemeral(
-                    &ctx,
-                    &command,
-                    "実行するタイミングが正しくありません".to_string(),
-                )
-                .await;
+            if !session.game.is_playing() {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "invalid-timing")).await;
                 return;
             }
-            let argument = command
-                .data
-                .options
-                .iter()
-                .find(|opt| opt.name == "a")
-                .cloned();
-
-            let value = argument.unwrap().value;
-            let mut answer = "回答です。".to_string();
-            answer.push_str(value.as_str().unwrap());
-
-            bot.messages
-                .lock()
-                .await
-                .push(ChatCompletionMessage::new(Role::User, answer));
-
-            let response = bot
-                .openai_client
-                .send_request(&bot.messages.lock().await)
+            if session.has_team_threads() {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "team-play-in-thread"))
+                    .await;
+                return;
+            }
+            if !session.can_play(command.user.id) {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "not-participant")).await;
+                return;
+            }
+            let value = string_option(&command, "a").unwrap_or_default();
+            let Some(warned) =
+                screen_input(&ctx, &command, bot, Target::Answer, value, locale).await
+            else {
+                return;
+            };
+            let player = Player::from(&command.user);
+            let event = session
+                .game
+                .answer(&bot.openai_client, &player, value)
                 .await;
 
-            if let Ok(res) = response {
-                bot.messages
-                    .lock()
-                    .await
-                    .push(ChatCompletionMessage::new(Role::Assistant, res.to_string()));
-
-                if res.starts_with("正解です。") {
-                    let builder = create_result_message(&command.user, &res, bot, false).await;
-
-                    if let Err(e) = command.create_response(&ctx.http, builder).await {
-                        println!("Error sending interaction response: {:?}", e);
+            match event {
+                Ok(Event::Solved { .. }) => {
+                    let finish = Finish::Solved(&command.user);
+                    let reply = Reply::Command(&command);
+                    if !finish_game(&ctx, bot, command.channel_id, &mut session, finish, reply)
+                        .await
+                    {
                         return;
                     }
-
-                    *state = State::Waiting;
-                } else {
-                    respond_to_command(&ctx, &command, res).await;
                 }
+                Ok(Event::Incorrect { reply }) => {
+                    respond_to_command(&ctx, &command, reply).await;
+                }
+                _ => {
+                    respond_to_command(&ctx, &command, t(locale, "api-error")).await;
+                    return;
+                }
+            }
+            if warned {
+                followup_ephemeral(&ctx, &command, t(locale, "moderation-warned")).await;
+            }
+        }
+        "hint" => {
+            if !session.game.is_playing() {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "invalid-timing")).await;
+                return;
+            }
+            if session.has_team_threads() {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "team-play-in-thread"))
+                    .await;
+                return;
+            }
+            if !session.can_play(command.user.id) {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "not-participant")).await;
+                return;
+            }
+
+            if let Ok(Event::Hinted { count, hint }) = session.game.hint(&bot.openai_client).await {
+                let response_content = t_args(
+                    locale,
+                    "hint-message",
+                    &fluent_args!["count" => count, "hint" => hint],
+                );
+                respond_to_command(&ctx, &command, response_content).await;
             } else {
-                respond_to_command(
-                    &ctx,
-                    &command,
-                    "APIの返却値取得においてエラーが発生しました".to_string(),
-                )
-                .await;
+                respond_to_command(&ctx, &command, t(locale, "api-error")).await;
             }
         }
         "giveup" => {
-            let mut state = bot.state.lock().await;
-            if !matches!(*state, State::Playing) {
-                respond_to_command_ephemeral(
-                    &ctx,
-                    &command,
-                    "実行するタイミングが正しくありません".to_string(),
-                )
-                .await;
+            if session.team.is_some() || session.has_team_threads() {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "team-giveup-disabled"))
+                    .await;
+                return;
+            }
+            if session.game.give_up().is_err() {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "invalid-timing")).await;
                 return;
             }
-            *state = State::Waiting;
-
-            bot.messages.lock().await.push(ChatCompletionMessage::new(
-                Role::User,
-                "ギブアップです。".to_string(),
-            ));
-
-            let response = bot
-                .openai_client
-                .send_request(&bot.messages.lock().await)
-                .await;
-
-            if let Ok(res) = response {
-                bot.messages
-                    .lock()
-                    .await
-                    .push(ChatCompletionMessage::new(Role::Assistant, res.to_string()));
 
-                let builder = create_result_message(&command.user, &res, bot, true).await;
+            let finish = Finish::GaveUp(&command.user);
+            let reply = Reply::Command(&command);
+            finish_game(&ctx, bot, command.channel_id, &mut session, finish, reply).await;
+        }
+        "pause" => {
+            // チーム戦はチームごとのスレッドで同時に進むため、一時停止できない
+            if session.team.is_some() || session.has_team_threads() {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "team-pause-disabled"))
+                    .await;
+                return;
+            }
+            if !session.can_play(command.user.id) {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "not-participant")).await;
+                return;
+            }
+            if session.game.pause().is_err() {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "invalid-timing")).await;
+                return;
+            }
+            pause_timer(&mut session);
+            // 手番の制限時間も止める。再開したら今の手番から数え直す
+            session.turn_serial += 1;
+            bot.paused_games.save(command.channel_id, &session).await;
+
+            let response_content = t_args(
+                locale,
+                "game-paused",
+                &fluent_args!["name" => command.user.display_name()],
+            );
+            respond_to_command(&ctx, &command, response_content).await;
+        }
+        "resume" => {
+            if !session.can_play(command.user.id) {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "not-participant")).await;
+                return;
+            }
+            if session.game.resume().is_err() {
+                respond_to_command_ephemeral(&ctx, &command, t(locale, "invalid-timing")).await;
+                return;
+            }
+            // あらすじの生成に時間がかかるため先に応答を保留する
+            if let Err(why) = command.defer(&ctx.http).await {
+                error!("Cannot defer slash command: {}", why);
+                session.game.rollback();
+                return;
+            }
+            session.game.commit();
+            bot.paused_games.remove(command.channel_id).await;
 
-                if let Err(e) = command.create_response(&ctx.http, builder).await {
-                    println!("Error sending interaction response: {:?}", e);
-                }
+            // まだ質問していない場合や、あらすじを作れなかった場合は問題文だけを表示する
+            let recap = if session.game.qa_log.is_empty() {
+                None
             } else {
-                respond_to_command(
-                    &ctx,
-                    &command,
-                    "APIの返却値取得においてエラーが発生しました".to_string(),
+                match session.game.recap(&bot.openai_client).await {
+                    Ok(recap) => Some(recap),
+                    Err(e) => {
+                        error!("あらすじの生成に失敗しました: {}", e);
+                        None
+                    }
+                }
+            };
+            let problem = session.game.problem.clone();
+            let response_content = match recap {
+                Some(recap) => t_args(
+                    locale,
+                    "game-resumed-recap",
+                    &fluent_args!["recap" => recap, "problem" => problem],
+                ),
+                None => t_args(locale, "game-resumed", &fluent_args!["problem" => problem]),
+            };
+            edit_command_response(&ctx, &command, response_content).await;
+
+            resume_timer(&ctx, bot, command.channel_id, &mut session).await;
+            start_turns(&ctx, bot, command.channel_id, &session).await;
+        }
+        "chat" => {
+            let enabled = command
+                .data
+                .options
+                .iter()
+                .find(|opt| opt.name == "enabled")
+                .and_then(|opt| opt.value.as_bool())
+                .unwrap_or_default();
+            session.chat_mode = enabled;
+
+            let response_content = if enabled {
+                t_args(
+                    locale,
+                    "chat-enabled",
+                    &fluent_args!["prefixes" => bot.answer_prefixes.join(" / ")],
                 )
-                .await;
+            } else {
+                t(locale, "chat-disabled")
+            };
+
+            respond_to_command(&ctx, &command, response_content).await;
+        }
+        "team" => handle_team(&ctx, &command, bot, &mut session, locale).await,
+        "leaderboard" => {
+            let period = string_option(&command, "period")
+                .and_then(Period::parse)
+                .unwrap_or(Period::All);
+            let (embed, buttons) = leaderboard_message(
+                &bot.leaderboard,
+                period,
+                0,
+                command.channel_id,
+                session.started_at,
+                locale,
+            )
+            .await;
+
+            let mut data = CreateInteractionResponseMessage::new().embed(embed);
+            if let Some(buttons) = buttons {
+                data = data.components(vec![buttons]);
+            }
+            if let Err(why) = command
+                .create_response(&ctx.http, CreateInteractionResponse::Message(data))
+                .await
+            {
+                error!("Cannot respond to slash command: {}", why);
             }
         }
+        "library" => {
+            let puzzles = bot.library.top_rated(LIBRARY_LIMIT).await;
+            let data =
+                CreateInteractionResponseMessage::new().embed(library_embed(&puzzles, locale));
+            if let Err(why) = command
+                .create_response(&ctx.http, CreateInteractionResponse::Message(data))
+                .await
+            {
+                error!("Cannot respond to slash command: {}", why);
+            }
+        }
+        "display" => {
+            session.display = match string_option(&command, "mode") {
+                Some("compact") => DisplayMode::Compact,
+                _ => DisplayMode::Verbose,
+            };
+
+            let response_content = match session.display {
+                DisplayMode::Verbose => t(locale, "display-verbose"),
+                DisplayMode::Compact => t(locale, "display-compact"),
+            };
+
+            respond_to_command(&ctx, &command, response_content).await;
+        }
         _ => {}
     };
 }
 
-async fn create_result_message(
-    user: &User,
-    description: &str,
+// 問題専用のスレッドを作成し、そのスレッドのセッションで出題する
+async fn play_in_thread(
+    ctx: &Context,
+    command: &CommandInteraction,
     bot: &Bot,
-    is_giveup: bool,
-) -> CreateInteractionResponse {
-    let next_button = CreateButton::new("next_button")
-        .label("次の問題に進む")
-        .style(ButtonStyle::Primary);
+    locale: Locale,
+    kind: ChannelType,
+) {
+    // スレッドの作成と問題の生成に時間がかかるため先に応答を保留する
+    if let Err(why) = command.defer(&ctx.http).await {
+        error!("Cannot defer slash command: {}", why);
+        return;
+    }
 
-    let cancel_button = CreateButton::new("cancel_button")
-        .label("終了する")
-        .style(ButtonStyle::Danger);
+    let builder = CreateThread::new(t(locale, "thread-name")).kind(kind);
+    let thread = match command.channel_id.create_thread(&ctx.http, builder).await {
+        Ok(thread) => thread,
+        Err(why) => {
+            error!("スレッドの作成に失敗しました: {}", why);
+            edit_command_response(ctx, command, t(locale, "thread-create-failed")).await;
+            return;
+        }
+    };
 
-    let action_row = CreateActionRow::Buttons(vec![next_button, cancel_button]);
+    if kind == ChannelType::PrivateThread {
+        if let Err(why) = thread
+            .id
+            .add_thread_member(&ctx.http, command.user.id)
+            .await
+        {
+            error!("スレッドへのメンバー追加に失敗しました: {}", why);
+        }
+    }
 
-    // giveupかanswerか
-    let mut message = if is_giveup {
-        "残念、ギブアップです😢\n".to_string()
-    } else {
-        format!(
-            "おめでとうございます🎉\n{}さん正解です！\n\n",
-            user.mention()
+    let settings = bot.channel_settings(command.channel_id).await;
+    let game_locale = settings.locale.unwrap_or(locale);
+    let session = bot
+        .insert_session(
+            thread.id,
+            Session::in_thread(game_locale, command.channel_id, settings),
         )
-    };
-    message.push_str(&format!("問題のストーリー\n{}", description));
+        .await;
+    let mut session = session.lock().await;
+
+    let _ = session.game.generate();
+    let question = generate_question(bot, &mut session, game_locale, thread.id).await;
+    let sent = thread
+        .id
+        .say(&ctx.http, question_message(&question, game_locale))
+        .await;
+    if let Err(why) = &sent {
+        error!("Error sending message: {:?}", why);
+    }
+    if question.is_ok() && sent.is_ok() {
+        let _ = session.game.start();
+        bot.record_start(thread.id, &session).await;
+        start_timer(ctx, bot, thread.id, &mut session).await;
+        start_turns(ctx, bot, thread.id, &session).await;
+    } else {
+        session.game.rollback();
+    }
 
-    let display_name = match user.global_name.clone() {
-        Some(name) => name,
-        None => user.name.clone(),
-    };
+    let content = t_args(
+        locale,
+        "thread-created",
+        &fluent_args!["thread" => thread.id.mention().to_string()],
+    );
+    edit_command_response(ctx, command, content).await;
+}
 
-    let mut scores = bot.scores.lock().await;
-    if !is_giveup {
-        if scores.contains_key(&display_name) {
-            let score = scores.get_mut(&display_name).unwrap();
-            *score += 1;
-        } else {
-            scores.insert(display_name.clone(), 1);
+// 質問・回答をモデレーションにかける
+// ブロックした場合はその旨を返答してNone、警告する場合はSome(true)を返す
+async fn screen_input(
+    ctx: &Context,
+    command: &CommandInteraction,
+    bot: &Bot,
+    target: Target,
+    text: &str,
+    locale: Locale,
+) -> Option<bool> {
+    match bot
+        .moderation
+        .screen(
+            &bot.openai_client,
+            target,
+            text,
+            command.channel_id,
+            Some(command.user.id),
+        )
+        .await
+    {
+        Some(ModerationAction::Warn) => Some(true),
+        Some(_) => {
+            respond_to_command_ephemeral(ctx, command, t(locale, "moderation-blocked")).await;
+            None
         }
+        None => Some(false),
     }
+}
 
-    let mut sorted_scores: Vec<_> = scores.iter().collect();
-    sorted_scores.sort_by(|a, b| b.1.cmp(a.1));
-
-    let fields: Vec<(String, String, bool)> = sorted_scores
+fn string_option<'a>(command: &'a CommandInteraction, name: &str) -> Option<&'a str> {
+    command
+        .data
+        .options
         .iter()
-        .map(|(user, score)| ((*user).clone(), format!("{}問正解", score), false))
-        .collect();
-
-    let mut embed = CreateEmbed::new().color(0x00ff00).description(message);
-
-    if !fields.is_empty() {
-        embed = embed.fields(fields);
-    }
+        .find(|opt| opt.name == name)
+        .and_then(|opt| opt.value.as_str())
+}
 
-    CreateInteractionResponse::Message(
-        CreateInteractionResponseMessage::new()
-            .embeds(vec![embed])
-            .components(vec![action_row]),
-    )
+fn is_thread(command: &CommandInteraction) -> bool {
+    command.channel.as_ref().is_some_and(|channel| {
+        matches!(
+            channel.kind,
+            ChannelType::PublicThread | ChannelType::PrivateThread | ChannelType::NewsThread
+        )
+    })
 }
 
 async fn respond_to_command(ctx: &Context, command: &CommandInteraction, response_content: String) {
@@ -268,8 +581,7 @@ async fn respond_to_command(ctx: &Context, command: &CommandInteraction, respons
     let builder = CreateInteractionResponse::Message(data);
 
     if let Err(why) = command.create_response(&ctx.http, builder).await {
-        println!("Cannot respond to slash command: {}", why);
-        println!("command.data: {:?}", command.data);
+        error!("Cannot respond to slash command: {}", why);
     }
 }
 
@@ -284,7 +596,28 @@ async fn respond_to_command_ephemeral(
     let builder = CreateInteractionResponse::Message(data);
 
     if let Err(why) = command.create_response(&ctx.http, builder).await {
-        println!("Cannot respond to slash command: {}", why);
-        println!("command.data: {:?}", command.data);
+        error!("Cannot respond to slash command: {}", why);
+    }
+}
+
+async fn followup_ephemeral(ctx: &Context, command: &CommandInteraction, content: String) {
+    let builder = CreateInteractionResponseFollowup::new()
+        .content(content)
+        .ephemeral(true);
+
+    if let Err(why) = command.create_followup(&ctx.http, builder).await {
+        error!("Cannot send followup message: {}", why);
+    }
+}
+
+async fn edit_command_response(
+    ctx: &Context,
+    command: &CommandInteraction,
+    response_content: String,
+) {
+    let builder = EditInteractionResponse::new().content(response_content);
+
+    if let Err(why) = command.edit_response(&ctx.http, builder).await {
+        error!("Cannot edit slash command response: {}", why);
     }
 }
@@ -1,12 +1,46 @@
+use fluent::fluent_args;
 use serenity::all::ComponentInteraction;
-use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::builder::{
+    CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage,
+};
 use serenity::prelude::*;
+use tracing::error;
 
+use crate::game::State;
 use crate::handlers::Bot;
-use crate::models::State;
-use crate::utils::question_generator::generate_question_builder;
+use crate::i18n::{t, t_args, Locale};
+use crate::leaderboard::Period;
+use crate::library::Feedback;
+use crate::submissions::SubmissionStatus;
+use crate::utils::leaderboard_message::leaderboard_message;
+use crate::utils::question_generator::generate_question;
+use crate::utils::question_message::question_response;
+use crate::utils::submission_message::review_embed;
+use crate::utils::thread::close_thread;
+use crate::utils::timer::start_timer;
+use crate::utils::turn::start_turns;
 
 pub async fn handle_component(ctx: Context, component: ComponentInteraction, bot: &Bot) {
+    // ランキングのページ送り（leaderboard:<期間>:<ページ>）
+    if let Some(args) = component.data.custom_id.strip_prefix("leaderboard:") {
+        let args = args.to_string();
+        leaderboard_page(component, ctx, bot, &args).await;
+        return;
+    }
+    // 問題の評価（rate:<問題のID>:<星の数か評価>）
+    if let Some(args) = component.data.custom_id.strip_prefix("rate:") {
+        let args = args.to_string();
+        rate_button(component, ctx, bot, &args).await;
+        return;
+    }
+
+    // 投稿された問題の審査（review:<投稿のID>:<approve|reject>）
+    if let Some(args) = component.data.custom_id.strip_prefix("review:") {
+        let args = args.to_string();
+        review_button(component, ctx, bot, &args).await;
+        return;
+    }
+
     match component.data.custom_id.as_str() {
         "next_button" => next_button(component, ctx, bot).await,
         "cancel_button" => finish_button(component, ctx, bot).await,
@@ -15,51 +49,208 @@ pub async fn handle_component(ctx: Context, component: ComponentInteraction, bot
 }
 
 async fn next_button(component: ComponentInteraction, ctx: Context, bot: &Bot) {
-    let mut state = bot.state.lock().await;
-    if !matches!(*state, State::Waiting) {
-        respond_to_component_ephemeral(
-            &ctx,
-            &component,
-            "実行するタイミングが正しくありません".to_string(),
-        )
-        .await;
+    let locale = Locale::from_interaction(&component.locale, component.guild_locale.as_deref());
+    let session = bot.session(component.channel_id).await;
+    let mut session = session.lock().await;
+    if session.game.state() != State::Waiting || session.game.generate().is_err() {
+        respond_to_component_ephemeral(&ctx, &component, t(locale, "invalid-timing")).await;
         return;
     }
 
-    let builder = generate_question_builder(bot).await;
-    if let Err(why) = component.create_response(&ctx.http, builder).await {
-        println!("次の問題の生成に失敗しました: {}", why);
-        println!("component.data: {:?}", component.data);
+    // 批評や作り直しで問題の生成に時間がかかるため、新しいメッセージとして先に応答を保留する
+    let defer = CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new());
+    if let Err(why) = component.create_response(&ctx.http, defer).await {
+        error!("Cannot defer component interaction: {}", why);
+        session.game.rollback();
+        return;
+    }
+
+    let session_locale = session.game.locale;
+    let question = generate_question(bot, &mut session, session_locale, component.channel_id).await;
+    let builder = question_response(&question, session_locale);
+    if let Err(why) = component.edit_response(&ctx.http, builder).await {
+        error!("次の問題の生成に失敗しました: {}", why);
+        session.game.rollback();
+        return;
+    }
+    // 出題できなかった場合はもう一度ボタンを押せるようにする
+    if question.is_err() {
+        session.game.rollback();
         return;
     }
 
-    *state = State::Playing;
+    let _ = session.game.start();
+    bot.record_start(component.channel_id, &session).await;
+    start_timer(&ctx, bot, component.channel_id, &mut session).await;
+    start_turns(&ctx, bot, component.channel_id, &session).await;
 }
 
 async fn finish_button(component: ComponentInteraction, ctx: Context, bot: &Bot) {
-    let mut state = bot.state.lock().await;
-    if !matches!(*state, State::Waiting) {
-        respond_to_component_ephemeral(
-            &ctx,
-            &component,
-            "実行するタイミングが正しくありません".to_string(),
-        )
-        .await;
+    let locale = Locale::from_interaction(&component.locale, component.guild_locale.as_deref());
+    let session = bot.session(component.channel_id).await;
+    let mut session = session.lock().await;
+    if session.game.state() != State::Waiting || session.game.finish().is_err() {
+        respond_to_component_ephemeral(&ctx, &component, t(locale, "invalid-timing")).await;
+        return;
+    }
+
+    respond_to_component(&ctx, &component, t(locale, "game-finished")).await;
+
+    if let Some(parent_channel) = session.parent_channel {
+        close_thread(&ctx, component.channel_id, parent_channel, &session).await;
+        bot.remove_session(component.channel_id).await;
+    }
+}
+
+async fn leaderboard_page(component: ComponentInteraction, ctx: Context, bot: &Bot, args: &str) {
+    let locale = Locale::from_interaction(&component.locale, component.guild_locale.as_deref());
+    let Some((period, page)) = args.split_once(':') else {
+        unknown_component(component, ctx).await;
+        return;
+    };
+    let (Some(period), Ok(page)) = (Period::parse(period), page.parse::<usize>()) else {
+        unknown_component(component, ctx).await;
+        return;
+    };
+
+    let session_started = match bot.find_session(component.channel_id).await {
+        Some(session) => session.lock().await.started_at,
+        None => i64::MAX,
+    };
+    let (embed, buttons) = leaderboard_message(
+        &bot.leaderboard,
+        period,
+        page,
+        component.channel_id,
+        session_started,
+        locale,
+    )
+    .await;
+
+    let mut data = CreateInteractionResponseMessage::new().embed(embed);
+    if let Some(buttons) = buttons {
+        data = data.components(vec![buttons]);
+    }
+    if let Err(why) = component
+        .create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(data))
+        .await
+    {
+        error!("ランキングの更新に失敗しました: {}", why);
+    }
+}
+
+async fn rate_button(component: ComponentInteraction, ctx: Context, bot: &Bot, args: &str) {
+    let locale = Locale::from_interaction(&component.locale, component.guild_locale.as_deref());
+    let Some((id, value)) = args.split_once(':') else {
+        unknown_component(component, ctx).await;
+        return;
+    };
+    let Ok(id) = id.parse::<u64>() else {
+        unknown_component(component, ctx).await;
+        return;
+    };
+    let stars = value.parse::<u8>().ok();
+    let feedback = Feedback::parse(value);
+    if stars.is_none() && feedback.is_none() {
+        unknown_component(component, ctx).await;
+        return;
+    }
+
+    let key = if bot
+        .library
+        .rate(id, component.user.id, stars, feedback)
+        .await
+    {
+        "rating-thanks"
+    } else {
+        "rating-not-found"
+    };
+    respond_to_component_ephemeral(&ctx, &component, t(locale, key)).await;
+}
+
+async fn review_button(component: ComponentInteraction, ctx: Context, bot: &Bot, args: &str) {
+    let locale = Locale::from_interaction(&component.locale, component.guild_locale.as_deref());
+    if !bot.is_moderator(component.member.as_ref()) {
+        respond_to_component_ephemeral(&ctx, &component, t(locale, "admin-forbidden")).await;
         return;
     }
+    let Some((id, action)) = args.split_once(':') else {
+        unknown_component(component, ctx).await;
+        return;
+    };
+    let status = match action {
+        "approve" => SubmissionStatus::Approved,
+        "reject" => SubmissionStatus::Rejected,
+        _ => {
+            unknown_component(component, ctx).await;
+            return;
+        }
+    };
+    let Ok(id) = id.parse::<u64>() else {
+        unknown_component(component, ctx).await;
+        return;
+    };
+
+    let Some(mut submission) = bot.submissions.review(id, status).await else {
+        respond_to_component_ephemeral(&ctx, &component, t(locale, "submission-already-reviewed"))
+            .await;
+        return;
+    };
+    if status == SubmissionStatus::Approved {
+        let puzzle_id = bot.library.add_submission(&submission).await;
+        bot.submissions.set_puzzle_id(id, puzzle_id).await;
+        submission.puzzle_id = Some(puzzle_id);
+    }
 
-    respond_to_component(&ctx, &component, "ゲームを終了します".to_string()).await;
-    *state = State::Idle;
+    // 審査した人と結果を表示し、ボタンを消す
+    let reviewer = component
+        .user
+        .global_name
+        .clone()
+        .unwrap_or_else(|| component.user.name.clone());
+    let data = CreateInteractionResponseMessage::new()
+        .content(t_args(
+            locale,
+            "submission-reviewed-by",
+            &fluent_args!["name" => reviewer],
+        ))
+        .embed(review_embed(&submission, locale))
+        .components(vec![]);
+    if let Err(why) = component
+        .create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(data))
+        .await
+    {
+        error!("審査の結果の更新に失敗しました: {}", why);
+    }
+
+    let key = if status == SubmissionStatus::Approved {
+        "submission-approved-dm"
+    } else {
+        "submission-rejected-dm"
+    };
+    let notice = t_args(
+        submission.locale,
+        key,
+        &fluent_args!["title" => submission.title.clone()],
+    );
+    if let Err(why) = submission
+        .author_id
+        .direct_message(&ctx.http, CreateMessage::new().content(notice))
+        .await
+    {
+        error!("投稿者への通知に失敗しました: {:?}", why);
+    }
 }
 
-async fn unknown_component(component: ComponentInteraction, ctx: Context) -> () {
-    let msg: &str = "未知のコンポーネントが呼ばれました";
+async fn unknown_component(component: ComponentInteraction, ctx: Context) {
+    let locale = Locale::from_interaction(&component.locale, component.guild_locale.as_deref());
+    let msg = t(locale, "unknown-component");
 
     let builder =
         CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(msg));
 
     if let Err(why) = component.create_response(&ctx.http, builder).await {
-        println!("Cannot respond to component interaction: {}", why);
+        error!("Cannot respond to component interaction: {}", why);
     }
 }
 
@@ -72,8 +263,7 @@ async fn respond_to_component(
     let builder = CreateInteractionResponse::Message(data);
 
     if let Err(why) = component.create_response(&ctx.http, builder).await {
-        println!("コンポーネントの返答に失敗しました: {}", why);
-        println!("component.data: {:?}", component.data);
+        error!("コンポーネントの返答に失敗しました: {}", why);
     }
 }
 
@@ -88,7 +278,6 @@ async fn respond_to_component_ephemeral(
     let builder = CreateInteractionResponse::Message(data);
 
     if let Err(why) = component.create_response(&ctx.http, builder).await {
-        println!("コンポーネントの返答に失敗しました: {}", why);
-        println!("component.data: {:?}", component.data);
+        error!("コンポーネントの返答に失敗しました: {}", why);
     }
 }
@@ -0,0 +1,181 @@
+use fluent::fluent_args;
+use serenity::all::{CommandInteraction, ResolvedOption, ResolvedValue};
+use serenity::builder::{CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::prelude::*;
+use tracing::error;
+
+use crate::game::{Event, Player};
+use crate::handlers::Bot;
+use crate::i18n::{t, t_args, Locale};
+use crate::moderation::{ModerationAction, Target};
+use crate::utils::daily_message::daily_board_embed;
+use crate::utils::finish_game::{finish_game, Finish, Reply};
+
+// /daily のサブコマンドを実行する
+// 参加者はそれぞれ自分だけのセッションで今日の問題に挑み、返答は本人にだけ表示する
+pub async fn handle_daily(ctx: Context, command: CommandInteraction, bot: &Bot) {
+    let locale = Locale::from_interaction(&command.locale, command.guild_locale.as_deref());
+    let user_id = command.user.id;
+
+    let options = command.data.options();
+    let Some(ResolvedOption {
+        name,
+        value: ResolvedValue::SubCommand(sub_options),
+        ..
+    }) = options.first()
+    else {
+        return;
+    };
+    let text = sub_options.iter().find_map(|opt| match opt.value {
+        ResolvedValue::String(value) if matches!(opt.name, "q" | "a") => Some(value),
+        _ => None,
+    });
+
+    let Some(puzzle) = bot.daily.current().await else {
+        respond(&ctx, &command, t(locale, "daily-not-ready")).await;
+        return;
+    };
+
+    if *name == "board" {
+        let standings = bot.daily.board(puzzle.date).await;
+        respond_embed(
+            &ctx,
+            &command,
+            daily_board_embed(&puzzle, &standings, locale),
+        )
+        .await;
+        return;
+    }
+
+    if bot.daily.result(puzzle.date, user_id).await.is_some() {
+        respond(&ctx, &command, t(locale, "daily-finished")).await;
+        return;
+    }
+
+    let session = bot.daily_session(user_id, &puzzle).await;
+    let mut session = session.lock().await;
+    if !session.game.is_playing() {
+        respond(&ctx, &command, t(locale, "daily-finished")).await;
+        return;
+    }
+
+    let player = Player::from(&command.user);
+
+    match *name {
+        "question" => {
+            let value = text.unwrap_or_default();
+            if is_blocked(bot, &command, Target::Question, value).await {
+                respond(&ctx, &command, t(locale, "moderation-blocked")).await;
+                return;
+            }
+
+            match session
+                .game
+                .question(&bot.openai_client, &player, value)
+                .await
+            {
+                Ok(Event::Replied { verdict, reply }) => {
+                    let response_content = format!(
+                        "{} **Q{}.** {}\n{}",
+                        verdict.emoji(),
+                        session.game.qa_log.len(),
+                        value,
+                        reply
+                    );
+                    respond(&ctx, &command, response_content).await;
+                }
+                _ => respond(&ctx, &command, t(locale, "api-error")).await,
+            }
+        }
+        "answer" => {
+            let value = text.unwrap_or_default();
+            if is_blocked(bot, &command, Target::Answer, value).await {
+                respond(&ctx, &command, t(locale, "moderation-blocked")).await;
+                return;
+            }
+
+            match session
+                .game
+                .answer(&bot.openai_client, &player, value)
+                .await
+            {
+                Ok(Event::Solved { .. }) => {
+                    let finish = Finish::Solved(&command.user);
+                    let reply = Reply::Command(&command);
+                    finish_game(&ctx, bot, command.channel_id, &mut session, finish, reply).await;
+                }
+                Ok(Event::Incorrect { reply }) => respond(&ctx, &command, reply).await,
+                _ => respond(&ctx, &command, t(locale, "api-error")).await,
+            }
+        }
+        "hint" => match session.game.hint(&bot.openai_client).await {
+            Ok(Event::Hinted { count, hint }) => {
+                let response_content = t_args(
+                    locale,
+                    "hint-message",
+                    &fluent_args!["count" => count, "hint" => hint],
+                );
+                respond(&ctx, &command, response_content).await;
+            }
+            _ => respond(&ctx, &command, t(locale, "api-error")).await,
+        },
+        "giveup" => {
+            if session.game.give_up().is_err() {
+                respond(&ctx, &command, t(locale, "invalid-timing")).await;
+                return;
+            }
+            let finish = Finish::GaveUp(&command.user);
+            let reply = Reply::Command(&command);
+            finish_game(&ctx, bot, command.channel_id, &mut session, finish, reply).await;
+        }
+        _ => {}
+    }
+}
+
+async fn is_blocked(bot: &Bot, command: &CommandInteraction, target: Target, text: &str) -> bool {
+    matches!(
+        bot.moderation
+            .screen(
+                &bot.openai_client,
+                target,
+                text,
+                command.channel_id,
+                Some(command.user.id),
+            )
+            .await,
+        Some(ModerationAction::Block | ModerationAction::Regenerate)
+    )
+}
+
+async fn respond(ctx: &Context, command: &CommandInteraction, response_content: String) {
+    try_respond(ctx, command, response_content).await;
+}
+
+// 返答できたかどうかを返す。返答できた場合だけ結果を記録するのに使う
+async fn try_respond(
+    ctx: &Context,
+    command: &CommandInteraction,
+    response_content: String,
+) -> bool {
+    let data = CreateInteractionResponseMessage::new()
+        .content(response_content)
+        .ephemeral(true);
+    let builder = CreateInteractionResponse::Message(data);
+
+    if let Err(why) = command.create_response(&ctx.http, builder).await {
+        error!("Cannot respond to slash command: {}", why);
+        return false;
+    }
+    true
+}
+
+async fn respond_embed(ctx: &Context, command: &CommandInteraction, embed: CreateEmbed) {
+    let data = CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .ephemeral(true);
+    let builder = CreateInteractionResponse::Message(data);
+
+    if let Err(why) = command.create_response(&ctx.http, builder).await {
+        error!("Cannot respond to slash command: {}", why);
+    }
+}
@@ -1,14 +1,19 @@
-use serenity::all::Timestamp;
-use serenity::builder::{CreateEmbed, CreateEmbedFooter, CreateMessage};
 use serenity::model::channel::Message;
 use serenity::prelude::*;
 use tracing::error;
 
-pub async fn handle_message(ctx: Context, msg: Message) {
+use crate::game::{Event, Player};
+use crate::handlers::Bot;
+use crate::i18n::t;
+use crate::models::{DisplayMode, Verdict};
+use crate::moderation::{ModerationAction, Target};
+use crate::utils::finish_game::{finish_game, Finish, Reply};
+use crate::utils::qa_board::update_board;
+use crate::utils::turn::{advance_turn, turn_notice};
+
+pub async fn handle_message(ctx: Context, msg: Message, bot: &Bot) {
     if msg.content == "!nurupo" {
-        let res = format!(
-            "{}",
-            r"
+        let res = r"
 ```
 　 　＿＿＿＿＿　　　　　 ／￣￣￣￣￣￣￣￣￣￣￣￣
 　／:＼.＿＿＿＿＼ 　／　
@@ -21,8 +26,7 @@ pub async fn handle_message(ctx: Context, msg: Message) {
 　＿＿＿＿＿___
 　|:￣＼　　　　 　＼ 　　＜ﾇﾙﾎﾟﾇﾙﾎﾟｰ!!
 　|:　　 |:￣￣￣￣:|
-```"
-        );
+```";
 
         if let Err(e) = msg.channel_id.say(&ctx.http, res).await {
             error!("Error sending message: {:?}", e);
@@ -30,9 +34,7 @@ pub async fn handle_message(ctx: Context, msg: Message) {
     }
 
     if msg.content == "!ga" {
-        let res = format!(
-            "{}",
-            r"
+        let res = r"
 ```
 　　 （　・∀・）　　　|　|　ｶﾞｯ\n\
 　　と　　　　）　 　 |　|\n\
@@ -40,54 +42,127 @@ pub async fn handle_message(ctx: Context, msg: Message) {
 　　　　 /　）　 　 < 　>__Λ∩\n\
 　　 ＿/し'　／／. Ｖ｀Д´）/\n\
 　　（＿フ彡　　　　　 　　/\n\
-```"
-        );
+```";
 
         if let Err(e) = msg.channel_id.say(&ctx.http, res).await {
             error!("Error sending message: {:?}", e);
         }
     }
 
-    if msg.content == "!embed" {
-        // The create message builder allows you to easily create embeds and messages using a
-        // builder syntax.
-        // This example will create a message that says "Hello, World!", with an embed that has
-        // a title, description, an image, three fields, and a footer.
-        let footer = CreateEmbedFooter::new("This is a footer");
-        let embed = CreateEmbed::new()
-            .title("This is a title")
-            .description("This is a description")
-            .fields(vec![
-                ("This is the first field", "This is a field body", true),
-                ("This is the second field", "Both fields are inline", true),
-            ])
-            .field(
-                "This is the third field",
-                "This is not an inline field",
-                false,
-            )
-            .footer(footer)
-            // Add a timestamp for the current time
-            // This also accepts a rfc3339 Timestamp
-            .timestamp(Timestamp::now());
-        let builder = CreateMessage::new().content("Hello, World!").embed(embed);
-
-        let msg = msg.channel_id.send_message(&ctx.http, builder).await;
-
-        if let Err(why) = msg {
-            println!("Error sending message: {why:?}");
+    if !msg.author.bot {
+        handle_chat_mode(&ctx, &msg, bot).await;
+    }
+}
+
+// チャットモードが有効なセッションでは、通常のメッセージを質問・回答として扱う
+async fn handle_chat_mode(ctx: &Context, msg: &Message, bot: &Bot) {
+    let Some(session) = bot.find_session(msg.channel_id).await else {
+        return;
+    };
+    let mut session = session.lock().await;
+    if !session.chat_mode || !session.game.is_playing() || !session.can_play(msg.author.id) {
+        return;
+    }
+    // チーム戦の親チャンネルでは、各チームのスレッドでしか質問・回答できない
+    if session.has_team_threads() {
+        return;
+    }
+
+    let content = msg.content.trim();
+    let answer = bot
+        .answer_prefixes
+        .iter()
+        .find_map(|prefix| content.strip_prefix(prefix.as_str()));
+    let is_question = content.ends_with('?') || content.ends_with('？');
+    if answer.is_none() && !is_question {
+        return;
+    }
+
+    let target = if answer.is_some() {
+        Target::Answer
+    } else {
+        Target::Question
+    };
+    match bot
+        .moderation
+        .screen(
+            &bot.openai_client,
+            target,
+            content,
+            msg.channel_id,
+            Some(msg.author.id),
+        )
+        .await
+    {
+        Some(ModerationAction::Warn) => react(ctx, msg, '⚠').await,
+        Some(_) => {
+            react(ctx, msg, '🚫').await;
+            return;
         }
+        None => {}
     }
 
-    if msg.content == "!typing" {
-        if let Err(e) = msg.channel_id.broadcast_typing(&ctx.http).await {
-            error!("Error broadcasting typing: {:?}", e);
+    let player = Player::from(&msg.author);
+    if let Some(answer) = answer {
+        let event = session
+            .game
+            .answer(&bot.openai_client, &player, answer)
+            .await;
+
+        match event {
+            Ok(Event::Solved { .. }) => {
+                let finish = Finish::Solved(&msg.author);
+                finish_game(
+                    ctx,
+                    bot,
+                    msg.channel_id,
+                    &mut session,
+                    finish,
+                    Reply::Channel,
+                )
+                .await;
+            }
+            Ok(_) => react(ctx, msg, Verdict::No.emoji()).await,
+            Err(_) => reply(ctx, msg, t(session.game.locale, "api-error")).await,
+        }
+    } else {
+        if session
+            .current_turn()
+            .is_some_and(|user| user != msg.author.id)
+        {
+            react(ctx, msg, '⏳').await;
+            return;
         }
+        let event = session
+            .game
+            .question(&bot.openai_client, &player, content)
+            .await;
 
-        tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+        match event {
+            Ok(Event::Replied { verdict, .. }) => {
+                react(ctx, msg, verdict.emoji()).await;
+                advance_turn(ctx, bot, msg.channel_id, &mut session).await;
+                if let Some(notice) = turn_notice(&session) {
+                    reply(ctx, msg, notice).await;
+                }
 
-        if let Err(e) = msg.channel_id.say(&ctx.http, "〇〇が入力中").await {
-            error!("Error sending message: {:?}", e);
+                if session.display == DisplayMode::Compact {
+                    update_board(ctx, msg.channel_id, &mut session).await;
+                }
+            }
+            _ => reply(ctx, msg, t(session.game.locale, "api-error")).await,
         }
     }
 }
+
+async fn react(ctx: &Context, msg: &Message, emoji: char) {
+    if let Err(e) = msg.react(&ctx.http, emoji).await {
+        error!("Error adding reaction: {:?}", e);
+    }
+}
+
+async fn reply(ctx: &Context, msg: &Message, content: String) {
+    if let Err(e) = msg.reply(&ctx.http, content).await {
+        error!("Error sending message: {:?}", e);
+    }
+}
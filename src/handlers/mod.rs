@@ -1,9 +1,21 @@
+pub mod admin;
 pub mod bot;
 pub mod command;
 pub mod component;
+pub mod daily;
 pub mod message;
+pub mod solo;
+pub mod submit;
+pub mod team;
+pub mod transcript;
 
-pub use bot::Bot;
+pub use admin::handle_admin;
+pub use bot::{Bot, BotKey};
 pub use command::handle_command;
 pub use component::handle_component;
+pub use daily::handle_daily;
 pub use message::handle_message;
+pub use solo::handle_solo;
+pub use submit::{handle_modal, handle_submit};
+pub use team::handle_team;
+pub use transcript::{handle_export, handle_import};
@@ -0,0 +1,257 @@
+use fluent::fluent_args;
+use serenity::all::{CommandInteraction, ResolvedOption, ResolvedValue};
+use serenity::builder::{
+    CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage,
+    EditInteractionResponse,
+};
+use serenity::prelude::*;
+use tracing::error;
+
+use crate::game::{Event, Player};
+use crate::handlers::Bot;
+use crate::i18n::{t, t_args, Locale};
+use crate::models::{ChannelSettings, Difficulty, SoloStats};
+use crate::moderation::{ModerationAction, Target};
+use crate::utils::finish_game::{finish_game, Finish, Reply};
+use crate::utils::question_generator::generate_question;
+use crate::utils::question_message::question_message;
+
+// /solo のサブコマンドを実行する
+// チャンネルのゲームとは別に、ユーザーごとのセッションで一人で遊ぶ。返答はすべて本人にだけ表示する
+pub async fn handle_solo(ctx: Context, command: CommandInteraction, bot: &Bot) {
+    let locale = Locale::from_interaction(&command.locale, command.guild_locale.as_deref());
+    let user_id = command.user.id;
+
+    let options = command.data.options();
+    let Some(ResolvedOption {
+        name,
+        value: ResolvedValue::SubCommand(sub_options),
+        ..
+    }) = options.first()
+    else {
+        return;
+    };
+    let text = sub_options.iter().find_map(|opt| match opt.value {
+        ResolvedValue::String(value) if matches!(opt.name, "q" | "a") => Some(value),
+        _ => None,
+    });
+
+    if *name == "stats" {
+        let stats = bot.solo_stats(user_id).await;
+        respond_embed(&ctx, &command, stats_embed(&stats, locale)).await;
+        return;
+    }
+
+    let session = bot.solo_session(user_id).await;
+    let mut session = session.lock().await;
+
+    match *name {
+        // 出題中の問題を終わらせてから生成すると、生成に失敗したときに遊んでいた問題が失われる
+        "start" if session.game.is_playing() => {
+            respond(&ctx, &command, t(locale, "solo-already-playing")).await;
+        }
+        "start" => {
+            // 問題の生成に時間がかかるため先に応答を保留する
+            if let Err(why) = command.defer_ephemeral(&ctx.http).await {
+                error!("Cannot defer slash command: {}", why);
+                return;
+            }
+
+            let difficulty = sub_options
+                .iter()
+                .find_map(|opt| match opt.value {
+                    ResolvedValue::String(value) if opt.name == "difficulty" => {
+                        Difficulty::parse(value)
+                    }
+                    _ => None,
+                })
+                .unwrap_or_default();
+            session.settings = ChannelSettings {
+                difficulty,
+                ..Default::default()
+            };
+
+            // 正解・ギブアップ後の問題は終わらせて新しい問題にする
+            let _ = session.game.finish();
+            let _ = session.game.generate();
+            let question = generate_question(bot, &mut session, locale, command.channel_id).await;
+            let shown = edit_response(&ctx, &command, question_message(&question, locale)).await;
+            // 問題を表示できてから挑戦数に数える
+            if question.is_ok() && shown {
+                let _ = session.game.start();
+                bot.update_solo_stats(user_id, |stats| stats.played += 1)
+                    .await;
+            } else {
+                session.game.rollback();
+            }
+        }
+        "question" | "answer" | "hint" | "giveup" if !session.game.is_playing() => {
+            respond(&ctx, &command, t(locale, "solo-not-playing")).await;
+        }
+        "question" => {
+            let value = text.unwrap_or_default();
+            if is_blocked(bot, &command, Target::Question, value).await {
+                respond(&ctx, &command, t(locale, "moderation-blocked")).await;
+                return;
+            }
+
+            let player = Player::from(&command.user);
+            match session
+                .game
+                .question(&bot.openai_client, &player, value)
+                .await
+            {
+                Ok(Event::Replied { verdict, reply }) => {
+                    let response_content = format!(
+                        "{} **Q{}.** {}\n{}",
+                        verdict.emoji(),
+                        session.game.qa_log.len(),
+                        value,
+                        reply
+                    );
+                    respond(&ctx, &command, response_content).await;
+                }
+                _ => respond(&ctx, &command, t(locale, "api-error")).await,
+            }
+        }
+        "answer" => {
+            let value = text.unwrap_or_default();
+            if is_blocked(bot, &command, Target::Answer, value).await {
+                respond(&ctx, &command, t(locale, "moderation-blocked")).await;
+                return;
+            }
+
+            let player = Player::from(&command.user);
+            match session
+                .game
+                .answer(&bot.openai_client, &player, value)
+                .await
+            {
+                Ok(Event::Solved { .. }) => {
+                    let finish = Finish::Solved(&command.user);
+                    let reply = Reply::Command(&command);
+                    finish_game(&ctx, bot, command.channel_id, &mut session, finish, reply).await;
+                }
+                Ok(Event::Incorrect { reply }) => respond(&ctx, &command, reply).await,
+                _ => respond(&ctx, &command, t(locale, "api-error")).await,
+            }
+        }
+        "hint" => match session.game.hint(&bot.openai_client).await {
+            Ok(Event::Hinted { count, hint }) => {
+                let response_content = t_args(
+                    locale,
+                    "hint-message",
+                    &fluent_args!["count" => count, "hint" => hint],
+                );
+                respond(&ctx, &command, response_content).await;
+            }
+            _ => respond(&ctx, &command, t(locale, "api-error")).await,
+        },
+        "giveup" => {
+            if session.game.give_up().is_err() {
+                respond(&ctx, &command, t(locale, "invalid-timing")).await;
+                return;
+            }
+            let finish = Finish::GaveUp(&command.user);
+            let reply = Reply::Command(&command);
+            finish_game(&ctx, bot, command.channel_id, &mut session, finish, reply).await;
+        }
+        _ => {}
+    }
+}
+
+async fn is_blocked(bot: &Bot, command: &CommandInteraction, target: Target, text: &str) -> bool {
+    matches!(
+        bot.moderation
+            .screen(
+                &bot.openai_client,
+                target,
+                text,
+                command.channel_id,
+                Some(command.user.id),
+            )
+            .await,
+        Some(ModerationAction::Block | ModerationAction::Regenerate)
+    )
+}
+
+fn stats_embed(stats: &SoloStats, locale: Locale) -> CreateEmbed {
+    let best = match stats.best {
+        Some(best) => best.to_string(),
+        None => "-".to_string(),
+    };
+
+    CreateEmbed::new()
+        .color(0x0099ff)
+        .title(t(locale, "solo-stats-title"))
+        .field(
+            t(locale, "solo-stats-played"),
+            stats.played.to_string(),
+            true,
+        )
+        .field(
+            t(locale, "solo-stats-solved"),
+            stats.solved.to_string(),
+            true,
+        )
+        .field(
+            t(locale, "solo-stats-gave-up"),
+            stats.gave_up.to_string(),
+            true,
+        )
+        .field(
+            t(locale, "solo-stats-average"),
+            format!("{:.1}", stats.average_questions()),
+            true,
+        )
+        .field(t(locale, "solo-stats-best"), best, true)
+        .field(t(locale, "solo-stats-hints"), stats.hints.to_string(), true)
+}
+
+async fn respond(ctx: &Context, command: &CommandInteraction, response_content: String) {
+    let data = CreateInteractionResponseMessage::new()
+        .content(response_content)
+        .ephemeral(true);
+    let builder = CreateInteractionResponse::Message(data);
+
+    if let Err(why) = command.create_response(&ctx.http, builder).await {
+        error!("Cannot respond to slash command: {}", why);
+    }
+}
+
+async fn respond_embed(ctx: &Context, command: &CommandInteraction, embed: CreateEmbed) {
+    try_respond_embed(ctx, command, embed).await;
+}
+
+// 返答できたかどうかを返す。返答できた場合だけ成績を記録するのに使う
+async fn try_respond_embed(
+    ctx: &Context,
+    command: &CommandInteraction,
+    embed: CreateEmbed,
+) -> bool {
+    let data = CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .ephemeral(true);
+    let builder = CreateInteractionResponse::Message(data);
+
+    if let Err(why) = command.create_response(&ctx.http, builder).await {
+        error!("Cannot respond to slash command: {}", why);
+        return false;
+    }
+    true
+}
+
+// 表示できたかどうかを返す
+async fn edit_response(
+    ctx: &Context,
+    command: &CommandInteraction,
+    response_content: String,
+) -> bool {
+    let builder = EditInteractionResponse::new().content(response_content);
+
+    if let Err(why) = command.edit_response(&ctx.http, builder).await {
+        error!("Cannot edit slash command response: {}", why);
+        return false;
+    }
+    true
+}
@@ -0,0 +1,204 @@
+use fluent::fluent_args;
+use serenity::all::{
+    ActionRowComponent, CommandInteraction, InputTextStyle, ModalInteraction, ResolvedValue,
+};
+use serenity::builder::{
+    CreateActionRow, CreateInputText, CreateInteractionResponse, CreateInteractionResponseMessage,
+    CreateMessage, CreateModal,
+};
+use serenity::prelude::*;
+use tracing::error;
+
+use crate::handlers::Bot;
+use crate::i18n::{t, t_args, Locale};
+use crate::models::Difficulty;
+use crate::submissions::Draft;
+use crate::utils::submission_message::review_message;
+
+// 投稿できるヒントの数
+const HINT_LIMIT: usize = 5;
+
+// /submit を実行する
+// 問題を投稿するフォーム（モーダル）を開く。選んだ難易度はフォームのID（submit:<難易度>）で受け渡す
+pub async fn handle_submit(ctx: Context, command: CommandInteraction, bot: &Bot) {
+    let locale = Locale::from_interaction(&command.locale, command.guild_locale.as_deref());
+    if !bot
+        .is_channel_allowed(
+            command.channel_id,
+            command
+                .channel
+                .as_ref()
+                .and_then(|channel| channel.parent_id),
+        )
+        .await
+    {
+        respond(&ctx, &command, t(locale, "channel-not-allowed")).await;
+        return;
+    }
+
+    let difficulty = command
+        .data
+        .options()
+        .iter()
+        .find_map(|opt| match opt.value {
+            ResolvedValue::String(value) if opt.name == "difficulty" => Difficulty::parse(value),
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    let modal = CreateModal::new(
+        format!("submit:{}", difficulty.as_str()),
+        t(locale, "submission-modal-title"),
+    )
+    .components(
+        vec![
+            input(locale, InputTextStyle::Short, "title", 1, 50)
+                .placeholder(t(locale, "submission-modal-title-placeholder")),
+            input(locale, InputTextStyle::Paragraph, "problem", 10, 1000),
+            input(locale, InputTextStyle::Paragraph, "solution", 10, 1000),
+            input(locale, InputTextStyle::Paragraph, "hints", 0, 500)
+                .required(false)
+                .placeholder(t(locale, "submission-modal-hints-placeholder")),
+        ]
+        .into_iter()
+        .map(CreateActionRow::InputText)
+        .collect(),
+    );
+
+    if let Err(why) = command
+        .create_response(&ctx.http, CreateInteractionResponse::Modal(modal))
+        .await
+    {
+        error!("Cannot respond to slash command: {}", why);
+    }
+}
+
+// 投稿フォームの入力欄。ラベルは submission-modal-<ID>-label
+fn input(
+    locale: Locale,
+    style: InputTextStyle,
+    custom_id: &str,
+    min_length: u16,
+    max_length: u16,
+) -> CreateInputText {
+    CreateInputText::new(
+        style,
+        t(locale, &format!("submission-modal-{}-label", custom_id)),
+        custom_id,
+    )
+    .min_length(min_length)
+    .max_length(max_length)
+    .required(true)
+}
+
+// 投稿フォームの送信を受け付ける
+// 審査待ちに追加し、審査用のチャンネルが設定されていれば承認・却下ボタン付きで投稿する
+pub async fn handle_modal(ctx: Context, modal: ModalInteraction, bot: &Bot) {
+    let locale = Locale::from_interaction(&modal.locale, modal.guild_locale.as_deref());
+    let Some(difficulty) = modal
+        .data
+        .custom_id
+        .strip_prefix("submit:")
+        .and_then(Difficulty::parse)
+    else {
+        return;
+    };
+
+    let value = |custom_id: &str| {
+        modal
+            .data
+            .components
+            .iter()
+            .flat_map(|row| &row.components)
+            .find_map(|component| match component {
+                ActionRowComponent::InputText(input) if input.custom_id == custom_id => {
+                    input.value.as_deref().map(str::trim)
+                }
+                _ => None,
+            })
+            .unwrap_or_default()
+            .to_string()
+    };
+    let draft = Draft {
+        title: value("title"),
+        problem: value("problem"),
+        solution: value("solution"),
+        // ヒントは1行に1つ
+        hints: value("hints")
+            .lines()
+            .map(str::trim)
+            .filter(|hint| !hint.is_empty())
+            .take(HINT_LIMIT)
+            .map(str::to_string)
+            .collect(),
+        difficulty,
+        // 出題するチャンネルの言語で投稿されたものとして扱う
+        locale: bot
+            .channel_settings(modal.channel_id)
+            .await
+            .locale
+            .unwrap_or(locale),
+    };
+    if draft.title.is_empty() || draft.problem.is_empty() || draft.solution.is_empty() {
+        respond_to_modal(&ctx, &modal, t(locale, "submission-invalid")).await;
+        return;
+    }
+
+    let author_name = modal
+        .user
+        .global_name
+        .clone()
+        .unwrap_or_else(|| modal.user.name.clone());
+    let submission = bot.submissions.add(modal.user.id, author_name, draft).await;
+
+    respond_to_modal(
+        &ctx,
+        &modal,
+        t_args(
+            locale,
+            "submission-received",
+            &fluent_args!["title" => submission.title.clone()],
+        ),
+    )
+    .await;
+
+    if let Some(channel_id) = bot.review_channel_id {
+        let review_locale = bot
+            .channel_settings(channel_id)
+            .await
+            .locale
+            .unwrap_or_default();
+        let (embed, buttons) = review_message(&submission, review_locale);
+        if let Err(why) = channel_id
+            .send_message(
+                &ctx.http,
+                CreateMessage::new().embed(embed).components(buttons),
+            )
+            .await
+        {
+            error!("審査の依頼の投稿に失敗しました: {:?}", why);
+        }
+    }
+}
+
+async fn respond(ctx: &Context, command: &CommandInteraction, response_content: String) {
+    let data = CreateInteractionResponseMessage::new()
+        .content(response_content)
+        .ephemeral(true);
+    let builder = CreateInteractionResponse::Message(data);
+
+    if let Err(why) = command.create_response(&ctx.http, builder).await {
+        error!("Cannot respond to slash command: {}", why);
+    }
+}
+
+async fn respond_to_modal(ctx: &Context, modal: &ModalInteraction, response_content: String) {
+    let data = CreateInteractionResponseMessage::new()
+        .content(response_content)
+        .ephemeral(true);
+    let builder = CreateInteractionResponse::Message(data);
+
+    if let Err(why) = modal.create_response(&ctx.http, builder).await {
+        error!("フォームの返答に失敗しました: {}", why);
+    }
+}
@@ -0,0 +1,285 @@
+use fluent::fluent_args;
+use serenity::all::{ChannelType, CommandInteraction, ResolvedOption, ResolvedValue};
+use serenity::builder::{
+    CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage, CreateThread,
+    EditInteractionResponse,
+};
+use serenity::prelude::*;
+use tracing::error;
+
+use crate::handlers::Bot;
+use crate::i18n::{t, t_args, Locale};
+use crate::models::{Session, Team};
+use crate::utils::question_generator::generate_question;
+use crate::utils::question_message::question_message;
+use crate::utils::turn::start_turns;
+
+// /team のサブコマンドを実行する
+// チームはチャンネルのセッションに保存され、チーム戦ではチームごとにスレッドを作って同じ問題に挑む
+pub async fn handle_team(
+    ctx: &Context,
+    command: &CommandInteraction,
+    bot: &Bot,
+    session: &mut Session,
+    locale: Locale,
+) {
+    let options = command.data.options();
+    let Some(ResolvedOption {
+        name,
+        value: ResolvedValue::SubCommand(sub_options),
+        ..
+    }) = options.first()
+    else {
+        return;
+    };
+    let team_name = sub_options.iter().find_map(|opt| match opt.value {
+        ResolvedValue::String(value) if opt.name == "name" => Some(value.trim().to_string()),
+        _ => None,
+    });
+
+    // チームを作る・チームに入ると参加者の募集中になる
+    if matches!(*name, "create" | "join") && session.game.join().is_err() {
+        respond_ephemeral(ctx, command, t(locale, "team-in-progress")).await;
+        return;
+    }
+
+    match *name {
+        "create" => {
+            let Some(team_name) = team_name.filter(|name| !name.is_empty()) else {
+                return;
+            };
+            if session.teams.iter().any(|team| team.name == team_name) {
+                let response_content =
+                    t_args(locale, "team-exists", &fluent_args!["team" => team_name]);
+                respond_ephemeral(ctx, command, response_content).await;
+                return;
+            }
+
+            leave_team(session, command);
+            session
+                .teams
+                .push(Team::new(team_name.clone(), command.user.id));
+
+            let response_content = t_args(
+                locale,
+                "team-created",
+                &fluent_args![
+                    "team" => team_name,
+                    "user" => command.user.mention().to_string()
+                ],
+            );
+            respond(ctx, command, response_content).await;
+        }
+        "join" => {
+            let Some(team_name) = team_name else {
+                return;
+            };
+            if !session.teams.iter().any(|team| team.name == team_name) {
+                let response_content =
+                    t_args(locale, "team-not-found", &fluent_args!["team" => team_name]);
+                respond_ephemeral(ctx, command, response_content).await;
+                return;
+            }
+
+            leave_team(session, command);
+            let Some(team) = session.teams.iter_mut().find(|team| team.name == team_name) else {
+                return;
+            };
+            team.members.push(command.user.id);
+
+            let response_content = t_args(
+                locale,
+                "team-joined",
+                &fluent_args![
+                    "team" => team_name,
+                    "user" => command.user.mention().to_string(),
+                    "count" => team.members.len()
+                ],
+            );
+            respond(ctx, command, response_content).await;
+        }
+        "list" => {
+            let description = if session.teams.is_empty() {
+                t(locale, "team-list-empty")
+            } else {
+                session
+                    .teams
+                    .iter()
+                    .map(|team| {
+                        let members = team
+                            .members
+                            .iter()
+                            .map(|member| member.mention().to_string())
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        t_args(
+                            locale,
+                            "team-list-line",
+                            &fluent_args![
+                                "team" => team.name.clone(),
+                                "score" => team.score,
+                                "members" => members
+                            ],
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            let embed = CreateEmbed::new()
+                .color(0x0099ff)
+                .title(t(locale, "team-list-title"))
+                .description(description);
+
+            let data = CreateInteractionResponseMessage::new().embed(embed);
+            if let Err(why) = command
+                .create_response(&ctx.http, CreateInteractionResponse::Message(data))
+                .await
+            {
+                error!("Cannot respond to slash command: {}", why);
+            }
+        }
+        "play" => play_team_game(ctx, command, bot, session, locale).await,
+        _ => {}
+    }
+}
+
+// 他のチームに入っている場合は抜ける。メンバーがいなくなったチームは削除する
+fn leave_team(session: &mut Session, command: &CommandInteraction) {
+    for team in &mut session.teams {
+        team.members.retain(|member| *member != command.user.id);
+    }
+    session.teams.retain(|team| !team.members.is_empty());
+}
+
+// 親チャンネルで問題を生成し、チームごとのスレッドに同じ問題を出題する
+async fn play_team_game(
+    ctx: &Context,
+    command: &CommandInteraction,
+    bot: &Bot,
+    session: &mut Session,
+    locale: Locale,
+) {
+    if session.parent_channel.is_some() {
+        respond_ephemeral(ctx, command, t(locale, "invalid-timing")).await;
+        return;
+    }
+    if session.teams.len() < 2 {
+        respond_ephemeral(ctx, command, t(locale, "team-not-enough")).await;
+        return;
+    }
+    if session.game.generate().is_err() {
+        respond_ephemeral(ctx, command, t(locale, "invalid-timing")).await;
+        return;
+    }
+
+    // 問題の生成とスレッドの作成に時間がかかるため先に応答を保留する
+    if let Err(why) = command.defer(&ctx.http).await {
+        error!("Cannot defer slash command: {}", why);
+        session.game.rollback();
+        return;
+    }
+
+    session.settings = bot.channel_settings(command.channel_id).await;
+    let game_locale = session.settings.locale.unwrap_or(locale);
+    let question = generate_question(bot, session, game_locale, command.channel_id).await;
+    if question.is_err() {
+        session.game.rollback();
+        edit_response(ctx, command, question_message(&question, game_locale)).await;
+        return;
+    }
+    let problem = question_message(&question, game_locale);
+
+    let mut created = vec![];
+    for (i, team) in session.teams.iter().enumerate() {
+        let name = t_args(
+            game_locale,
+            "team-thread-name",
+            &fluent_args!["team" => team.name.clone()],
+        );
+        let builder = CreateThread::new(name).kind(ChannelType::PrivateThread);
+        match command.channel_id.create_thread(&ctx.http, builder).await {
+            Ok(thread) => created.push((i, thread)),
+            Err(why) => error!("スレッドの作成に失敗しました: {}", why),
+        }
+    }
+    // どのチームのスレッドも作れなかった場合は、出題せずに元の状態に戻す
+    if created.is_empty() {
+        session.game.rollback();
+        edit_response(ctx, command, t(locale, "team-threads-failed")).await;
+        return;
+    }
+
+    // チームのスレッドは出題中の親チャンネルのゲームを引き継ぐ
+    let _ = session.game.start();
+    bot.record_start(command.channel_id, session).await;
+
+    let mut threads = vec![];
+    for (i, thread) in created {
+        let team = &session.teams[i];
+        let mut members = vec![];
+        for member in &team.members {
+            if let Err(why) = thread.id.add_thread_member(&ctx.http, *member).await {
+                error!("スレッドへのメンバー追加に失敗しました: {}", why);
+            }
+            members.push(member.mention().to_string());
+        }
+
+        let welcome = t_args(
+            game_locale,
+            "team-thread-welcome",
+            &fluent_args!["team" => team.name.clone(), "members" => members.join(" ")],
+        );
+        if let Err(why) = thread
+            .id
+            .say(&ctx.http, format!("{}\n\n{}", welcome, problem))
+            .await
+        {
+            error!("Error sending message: {:?}", why);
+        }
+
+        let thread_session = bot
+            .insert_session(
+                thread.id,
+                Session::for_team(session, command.channel_id, team),
+            )
+            .await;
+        start_turns(ctx, bot, thread.id, &*thread_session.lock().await).await;
+        session.teams[i].thread = Some(thread.id);
+        threads.push(thread.id.mention().to_string());
+    }
+
+    let content = t_args(
+        locale,
+        "team-game-started",
+        &fluent_args!["threads" => threads.join(" ")],
+    );
+    edit_response(ctx, command, content).await;
+}
+
+async fn respond(ctx: &Context, command: &CommandInteraction, response_content: String) {
+    let data = CreateInteractionResponseMessage::new().content(response_content);
+    let builder = CreateInteractionResponse::Message(data);
+
+    if let Err(why) = command.create_response(&ctx.http, builder).await {
+        error!("Cannot respond to slash command: {}", why);
+    }
+}
+
+async fn respond_ephemeral(ctx: &Context, command: &CommandInteraction, response_content: String) {
+    let data = CreateInteractionResponseMessage::new()
+        .content(response_content)
+        .ephemeral(true);
+    let builder = CreateInteractionResponse::Message(data);
+
+    if let Err(why) = command.create_response(&ctx.http, builder).await {
+        error!("Cannot respond to slash command: {}", why);
+    }
+}
+
+async fn edit_response(ctx: &Context, command: &CommandInteraction, response_content: String) {
+    let builder = EditInteractionResponse::new().content(response_content);
+
+    if let Err(why) = command.edit_response(&ctx.http, builder).await {
+        error!("Cannot edit slash command response: {}", why);
+    }
+}
@@ -0,0 +1,302 @@
+use fluent::fluent_args;
+use serenity::all::{ChannelId, CommandInteraction, ResolvedValue};
+use serenity::builder::{
+    CreateAttachment, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage,
+    EditInteractionResponse,
+};
+use serenity::prelude::*;
+use tracing::{error, warn};
+
+use crate::handlers::Bot;
+use crate::i18n::{t, t_args, Locale};
+use crate::moderation::Target;
+use crate::transcript::Transcript;
+use crate::utils::question_message::question_message;
+use crate::utils::timer::start_timer;
+use crate::utils::turn::start_turns;
+
+// /export の返答に表示する、このチャンネルの最近のゲームの数
+const RECENT_LIMIT: usize = 5;
+// /import で読み込むファイルの上限（バイト）
+const IMPORT_LIMIT: u32 = 1_000_000;
+// 読み込んだ記録の集計に表示する、質問した人の数
+const ASKER_LIMIT: usize = 5;
+
+// /export を実行する
+// 出題中の問題か、このチャンネルで終わった問題の記録をファイルで書き出す。出題中の問題は解答を含めない
+pub async fn handle_export(ctx: Context, command: CommandInteraction, bot: &Bot) {
+    let locale = Locale::from_interaction(&command.locale, command.guild_locale.as_deref());
+    let options = command.data.options();
+    let format = options.iter().find_map(|opt| match opt.value {
+        ResolvedValue::String(value) if opt.name == "format" => Some(value),
+        _ => None,
+    });
+    let game = options.iter().find_map(|opt| match opt.value {
+        ResolvedValue::Integer(id) if opt.name == "game" => Some(id as u64),
+        _ => None,
+    });
+
+    let recent = bot
+        .transcripts
+        .recent(command.channel_id, RECENT_LIMIT)
+        .await;
+    let transcript = match game {
+        Some(id) => bot
+            .transcripts
+            .get(id)
+            .await
+            .filter(|transcript| transcript.is_visible_from(command.channel_id)),
+        None => match current(bot, command.channel_id).await {
+            Some(transcript) => Some(transcript),
+            None => recent.first().cloned(),
+        },
+    };
+    let Some(transcript) = transcript else {
+        respond(&ctx, &command, t(locale, "export-not-found")).await;
+        return;
+    };
+
+    let name = if transcript.id == 0 {
+        "transcript-current".to_string()
+    } else {
+        format!("transcript-{}", transcript.id)
+    };
+    let mut files = vec![];
+    if format != Some("json") {
+        let markdown = transcript.to_markdown(locale, bot.timezone);
+        files.push(CreateAttachment::bytes(
+            markdown.into_bytes(),
+            format!("{}.md", name),
+        ));
+    }
+    if format != Some("markdown") {
+        match transcript.to_json() {
+            Ok(json) => files.push(CreateAttachment::bytes(
+                json.into_bytes(),
+                format!("{}.json", name),
+            )),
+            Err(e) => error!("トランスクリプトの書き出しに失敗しました: {:?}", e),
+        }
+    }
+
+    let content = if recent.is_empty() {
+        String::new()
+    } else {
+        let games = recent
+            .iter()
+            .map(|transcript| format!("#{}", transcript.id))
+            .collect::<Vec<_>>()
+            .join(", ");
+        t_args(locale, "export-recent", &fluent_args!["games" => games])
+    };
+    let data = CreateInteractionResponseMessage::new()
+        .content(content)
+        .files(files)
+        .ephemeral(true);
+    if let Err(why) = command
+        .create_response(&ctx.http, CreateInteractionResponse::Message(data))
+        .await
+    {
+        error!("Cannot respond to slash command: {}", why);
+    }
+}
+
+// 出題中の問題の記録
+async fn current(bot: &Bot, channel_id: ChannelId) -> Option<Transcript> {
+    let session = bot.find_session(channel_id).await?;
+    let session = session.lock().await;
+    session
+        .game
+        .is_playing()
+        .then(|| Transcript::from_session(0, channel_id, &session, None))
+}
+
+// /import を実行する
+// /export で書き出したJSONを読み込んで集計を表示する。playを指定した場合はその問題をこのチャンネルで出題し直す
+pub async fn handle_import(ctx: Context, command: CommandInteraction, bot: &Bot) {
+    let locale = Locale::from_interaction(&command.locale, command.guild_locale.as_deref());
+    let options = command.data.options();
+    let Some(attachment) = options.iter().find_map(|opt| match opt.value {
+        ResolvedValue::Attachment(attachment) if opt.name == "file" => Some(attachment),
+        _ => None,
+    }) else {
+        return;
+    };
+    let play = options
+        .iter()
+        .any(|opt| opt.name == "play" && matches!(opt.value, ResolvedValue::Boolean(true)));
+
+    if attachment.size > IMPORT_LIMIT {
+        respond(&ctx, &command, t(locale, "import-too-large")).await;
+        return;
+    }
+    let transcript = match attachment.download().await {
+        Ok(bytes) => String::from_utf8(bytes)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| Transcript::from_json(&json)),
+        Err(e) => Err(e.into()),
+    };
+    let transcript = match transcript {
+        Ok(transcript) => transcript,
+        Err(e) => {
+            warn!("トランスクリプトの読み込みに失敗しました: {:?}", e);
+            respond(&ctx, &command, t(locale, "import-failed")).await;
+            return;
+        }
+    };
+    let embed = summary_embed(&transcript, locale);
+
+    if !play {
+        let data = CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .ephemeral(true);
+        if let Err(why) = command
+            .create_response(&ctx.http, CreateInteractionResponse::Message(data))
+            .await
+        {
+            error!("Cannot respond to slash command: {}", why);
+        }
+        return;
+    }
+
+    let Some(solution) = transcript.solution.clone() else {
+        respond(&ctx, &command, t(locale, "import-no-solution")).await;
+        return;
+    };
+    let session = bot.session(command.channel_id).await;
+    let mut session = session.lock().await;
+    if session.game.generate().is_err() {
+        respond(&ctx, &command, t(locale, "invalid-timing")).await;
+        return;
+    }
+    if command.guild_id.is_none() {
+        session.dm_user = Some(command.user.id);
+    }
+
+    // モデレーションに時間がかかるため先に応答を保留する
+    if let Err(why) = command.defer(&ctx.http).await {
+        error!("Cannot defer slash command: {}", why);
+        session.game.rollback();
+        return;
+    }
+    // 読み込んだ問題は誰でも書き換えられるため、生成した問題と同じようにモデレーションにかける
+    let text = format!("{}\n{}", transcript.problem, solution);
+    if bot
+        .moderation
+        .screen(
+            &bot.openai_client,
+            Target::Puzzle,
+            &text,
+            command.channel_id,
+            Some(command.user.id),
+        )
+        .await
+        .is_some()
+    {
+        session.game.rollback();
+        edit_response(&ctx, &command, t(locale, "moderation-blocked")).await;
+        return;
+    }
+
+    session.settings = bot.channel_settings(command.channel_id).await;
+    session.settings.difficulty = transcript.difficulty;
+    let game_locale = transcript.locale;
+    session.reset_messages(game_locale, bot.system_prompt(game_locale).await);
+    session.game.problem = transcript.problem.clone();
+    session.game.solution = solution;
+    // 審査を経ていない問題が他のチャンネルで出題されないよう、ライブラリには記録しない
+    session.game.unlisted = true;
+
+    let content = format!(
+        "{}\n{}",
+        t(game_locale, "import-replay"),
+        question_message(&Ok(transcript.problem), game_locale)
+    );
+    let builder = EditInteractionResponse::new().content(content);
+    if let Err(why) = command.edit_response(&ctx.http, builder).await {
+        error!("Cannot edit slash command response: {}", why);
+        session.game.rollback();
+        return;
+    }
+    let _ = session.game.start();
+    bot.record_start(command.channel_id, &session).await;
+    start_timer(&ctx, bot, command.channel_id, &mut session).await;
+    start_turns(&ctx, bot, command.channel_id, &session).await;
+}
+
+// 読み込んだ記録の集計
+fn summary_embed(transcript: &Transcript, locale: Locale) -> CreateEmbed {
+    let summary = transcript.summary();
+    let askers = if summary.askers.is_empty() {
+        "-".to_string()
+    } else {
+        summary
+            .askers
+            .iter()
+            .take(ASKER_LIMIT)
+            .map(|(name, count)| format!("{} ({})", name, count))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    CreateEmbed::new()
+        .color(0x0099ff)
+        .title(t_args(
+            locale,
+            "import-summary-title",
+            &fluent_args!["id" => transcript.id],
+        ))
+        .description(&transcript.problem)
+        .field(
+            t(locale, "transcript-ending"),
+            transcript.ending_label(locale),
+            true,
+        )
+        .field(
+            t(locale, "import-summary-questions"),
+            t_args(
+                locale,
+                "import-summary-verdicts",
+                &fluent_args![
+                    "yes" => summary.yes,
+                    "no" => summary.no,
+                    "irrelevant" => summary.irrelevant
+                ],
+            ),
+            true,
+        )
+        .field(
+            t(locale, "transcript-hints"),
+            transcript.hints.len().to_string(),
+            true,
+        )
+        .field(
+            t(locale, "import-summary-duration"),
+            t_args(
+                locale,
+                "admin-config-minutes",
+                &fluent_args!["minutes" => summary.duration / 60],
+            ),
+            true,
+        )
+        .field(t(locale, "import-summary-askers"), askers, false)
+}
+
+async fn respond(ctx: &Context, command: &CommandInteraction, response_content: String) {
+    let data = CreateInteractionResponseMessage::new()
+        .content(response_content)
+        .ephemeral(true);
+    let builder = CreateInteractionResponse::Message(data);
+
+    if let Err(why) = command.create_response(&ctx.http, builder).await {
+        error!("Cannot respond to slash command: {}", why);
+    }
+}
+
+async fn edit_response(ctx: &Context, command: &CommandInteraction, response_content: String) {
+    let builder = EditInteractionResponse::new().content(response_content);
+
+    if let Err(why) = command.edit_response(&ctx.http, builder).await {
+        error!("Cannot edit slash command response: {}", why);
+    }
+}
@@ -0,0 +1,190 @@
+// メッセージカタログ（Fluent）の読み込みとロケール判定
+// ユーザー向けの文字列はすべて locales/*.ftl に定義する
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use fluent::concurrent::FluentBundle;
+use fluent::{FluentArgs, FluentResource};
+use serde::{Deserialize, Serialize};
+use serenity::all::CommandOptionType;
+use serenity::builder::{CreateCommand, CreateCommandOption};
+use unic_langid::LanguageIdentifier;
+
+type Bundle = FluentBundle<FluentResource>;
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    Ja,
+    En,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::Ja, Locale::En];
+
+    // Discordのロケールコード（"ja", "en-US"など）から対応するロケールを取得する
+    pub fn from_discord(code: &str) -> Option<Self> {
+        match code.split('-').next().unwrap_or_default() {
+            "ja" => Some(Locale::Ja),
+            "en" => Some(Locale::En),
+            _ => None,
+        }
+    }
+
+    // ユーザーのロケール、サーバーのロケール、デフォルトの順に対応しているものを選ぶ
+    pub fn from_interaction(user_locale: &str, guild_locale: Option<&str>) -> Self {
+        Self::from_discord(user_locale)
+            .or_else(|| guild_locale.and_then(Self::from_discord))
+            .unwrap_or_default()
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::Ja => "ja",
+            Locale::En => "en",
+        }
+    }
+
+    // コマンドのローカライズに使うDiscordのロケールコード
+    pub fn discord_codes(self) -> &'static [&'static str] {
+        match self {
+            Locale::Ja => &["ja"],
+            Locale::En => &["en-US", "en-GB"],
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Locale::Ja => include_str!("../../locales/ja.ftl"),
+            Locale::En => include_str!("../../locales/en.ftl"),
+        }
+    }
+
+    fn langid(self) -> LanguageIdentifier {
+        match self {
+            Locale::Ja => "ja",
+            Locale::En => "en-US",
+        }
+        .parse()
+        .expect("invalid language identifier")
+    }
+}
+
+fn bundles() -> &'static HashMap<Locale, Bundle> {
+    static BUNDLES: OnceLock<HashMap<Locale, Bundle>> = OnceLock::new();
+
+    BUNDLES.get_or_init(|| {
+        Locale::ALL
+            .iter()
+            .map(|&locale| {
+                let resource = FluentResource::try_new(locale.source().to_string())
+                    .expect("failed to parse the message catalog");
+                let mut bundle = Bundle::new_concurrent(vec![locale.langid()]);
+                // Discord上で方向制御文字が表示されてしまうため無効にする
+                bundle.set_use_isolating(false);
+                bundle
+                    .add_resource(resource)
+                    .expect("failed to add the message catalog");
+                (locale, bundle)
+            })
+            .collect()
+    })
+}
+
+// カタログからメッセージを取得する
+pub fn t(locale: Locale, key: &str) -> String {
+    format(locale, key, None)
+}
+
+// 引数付きでカタログからメッセージを取得する
+pub fn t_args(locale: Locale, key: &str, args: &FluentArgs) -> String {
+    format(locale, key, Some(args))
+}
+
+fn format(locale: Locale, key: &str, args: Option<&FluentArgs>) -> String {
+    let bundle = &bundles()[&locale];
+    let Some(pattern) = bundle.get_message(key).and_then(|message| message.value()) else {
+        tracing::warn!(
+            "message '{}' was not found in the {:?} catalog",
+            key,
+            locale
+        );
+        return key.to_string();
+    };
+
+    let mut errors = vec![];
+    let value = bundle.format_pattern(pattern, args, &mut errors);
+    if !errors.is_empty() {
+        tracing::warn!("failed to format '{}': {:?}", key, errors);
+    }
+
+    value.into_owned()
+}
+
+// 全ロケールの名前と説明を設定したコマンドを作成する
+// カタログのキーは `cmd-<name>-name` と `cmd-<name>-description`
+pub fn command(name: &str) -> CreateCommand {
+    let mut command =
+        CreateCommand::new(name).description(t(Locale::default(), &cmd_key(name, "description")));
+
+    for locale in Locale::ALL {
+        for code in locale.discord_codes() {
+            command = command
+                .name_localized(*code, t(locale, &cmd_key(name, "name")))
+                .description_localized(*code, t(locale, &cmd_key(name, "description")));
+        }
+    }
+
+    command
+}
+
+// 全ロケールの名前と説明を設定したオプションを作成する
+// カタログのキーは `opt-<command>-<name>-name` と `opt-<command>-<name>-description`
+pub fn option(kind: CommandOptionType, command: &str, name: &str) -> CreateCommandOption {
+    let key = format!("{}-{}", command, name);
+    let mut option = CreateCommandOption::new(
+        kind,
+        name,
+        t(Locale::default(), &opt_key(&key, "description")),
+    );
+
+    for locale in Locale::ALL {
+        for code in locale.discord_codes() {
+            option = option
+                .name_localized(*code, t(locale, &opt_key(&key, "name")))
+                .description_localized(*code, t(locale, &opt_key(&key, "description")));
+        }
+    }
+
+    option
+}
+
+// 全ロケールの表示名を設定した文字列の選択肢を追加する
+// カタログのキーは `choice-<command>-<option>-<value>`
+pub fn string_choice(
+    option: CreateCommandOption,
+    command: &str,
+    name: &str,
+    value: &str,
+) -> CreateCommandOption {
+    let key = format!("choice-{}-{}-{}", command, name, value);
+    let key = key.as_str();
+    let locales = Locale::ALL.iter().flat_map(|&locale| {
+        locale
+            .discord_codes()
+            .iter()
+            .map(move |code| (*code, t(locale, key)))
+    });
+
+    option.add_string_choice_localized(t(Locale::default(), key), value, locales)
+}
+
+fn cmd_key(name: &str, field: &str) -> String {
+    format!("cmd-{}-{}", name, field)
+}
+
+fn opt_key(name: &str, field: &str) -> String {
+    format!("opt-{}-{}", name, field)
+}
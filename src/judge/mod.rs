@@ -0,0 +1,298 @@
+// プレイヤーの質問・回答の判定
+// 問題を出題した会話履歴にはユーザーの入力を追加せず、解答を知っている判定役に都度リクエストする
+// 入力はタグで区切ってエスケープし、判定役の応答に解答が漏れていないかを確認してから返す
+
+use std::collections::HashSet;
+
+use crate::api::ChatBackend;
+use crate::constants::prompt::GamePrompt;
+use crate::models::{ChatCompletionMessage, Role, Verdict};
+
+const INPUT_OPEN: &str = "<player_input>";
+const INPUT_CLOSE: &str = "</player_input>";
+
+// 解答と（問題文にない部分で）これだけの文字数が連続して一致したら漏洩とみなす
+const LEAK_WINDOW: usize = 20;
+// 言い換えていても、解答のn-gramのうちこの割合を含んでいたら漏洩とみなす
+const LEAK_COVERAGE: f64 = 0.25;
+const NGRAM: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Question,
+    Answer,
+    // 入力にはこれまでに出したヒントを渡す
+    Hint,
+}
+
+// プレイヤーの入力をタグで囲む
+// タグを閉じて指示を書き足せないよう、山括弧は全角にして制御文字は取り除く
+pub fn delimit_input(input: &str) -> String {
+    let escaped: String = input
+        .trim()
+        .chars()
+        .filter(|c| !c.is_control() || *c == '\n')
+        .map(|c| match c {
+            '<' => '＜',
+            '>' => '＞',
+            c => c,
+        })
+        .collect();
+
+    format!("{}\n{}\n{}", INPUT_OPEN, escaped, INPUT_CLOSE)
+}
+
+// 判定役へのリクエスト。解答はシステムプロンプトにだけ含める
+pub fn judge_request(
+    prompt: &GamePrompt,
+    problem: &str,
+    solution: &str,
+    kind: Kind,
+    input: &str,
+) -> Vec<ChatCompletionMessage> {
+    let label = match kind {
+        Kind::Question => prompt.question,
+        Kind::Answer => prompt.answer,
+        Kind::Hint => prompt.hint,
+    };
+
+    vec![
+        ChatCompletionMessage::new(Role::System, prompt.judge_system_prompt(problem, solution)),
+        ChatCompletionMessage::new(
+            Role::User,
+            format!("{}\n{}", label.trim_end(), delimit_input(input)),
+        ),
+    ]
+}
+
+// 質問・回答を判定させ、解答が漏れていない応答を返す
+pub async fn judge(
+    backend: &dyn ChatBackend,
+    prompt: &GamePrompt,
+    problem: &str,
+    solution: &str,
+    kind: Kind,
+    input: &str,
+) -> Result<String, anyhow::Error> {
+    let messages = judge_request(prompt, problem, solution, kind, input);
+    let reply = backend.send_request(&messages).await?;
+
+    Ok(sanitize(&reply, prompt, problem, solution, kind))
+}
+
+// 正解の場合のストーリーは保存している解答から表示するため、判定役の応答は正誤だけにする
+// 解答が漏れている場合は はい・いいえ・不正解 だけの応答に置き換える
+fn sanitize(reply: &str, prompt: &GamePrompt, problem: &str, solution: &str, kind: Kind) -> String {
+    let reply = reply.trim();
+
+    match kind {
+        Kind::Answer if prompt.is_correct(reply) => prompt.correct.to_string(),
+        Kind::Answer if leaks_solution(reply, problem, solution) => prompt.incorrect.to_string(),
+        Kind::Hint if leaks_solution(reply, problem, solution) => prompt.no_hint.to_string(),
+        Kind::Question if leaks_solution(reply, problem, solution) => {
+            let index = match Verdict::parse(reply, prompt) {
+                Verdict::Yes => 0,
+                Verdict::No => 1,
+                Verdict::Irrelevant => 2,
+            };
+            prompt.replies[index].to_string()
+        }
+        _ => reply.to_string(),
+    }
+}
+
+// 応答に解答の一部がそのまま、または言い換えて含まれているか
+// 問題文にも書かれている部分は明かしても問題ないため除く
+pub fn leaks_solution(reply: &str, problem: &str, solution: &str) -> bool {
+    let reply = normalize(reply);
+    let problem = normalize(problem);
+    let solution = normalize(solution);
+
+    let problem_text: String = problem.iter().collect();
+    let solution_text: String = solution.iter().collect();
+    let verbatim = reply.windows(LEAK_WINDOW).any(|window| {
+        let window: String = window.iter().collect();
+        solution_text.contains(&window) && !problem_text.contains(&window)
+    });
+    if verbatim {
+        return true;
+    }
+
+    let problem_ngrams: HashSet<&[char]> = problem.windows(NGRAM).collect();
+    let solution_ngrams: HashSet<&[char]> = solution
+        .windows(NGRAM)
+        .filter(|ngram| !problem_ngrams.contains(ngram))
+        .collect();
+    if solution_ngrams.is_empty() {
+        return false;
+    }
+
+    let reply_ngrams: HashSet<&[char]> = reply.windows(NGRAM).collect();
+    let covered = solution_ngrams.intersection(&reply_ngrams).count();
+    covered as f64 / solution_ngrams.len() as f64 >= LEAK_COVERAGE
+}
+
+// 空白や句読点の違いで見逃さないよう、文字と数字だけを小文字にして残す
+fn normalize(text: &str) -> Vec<char> {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::scripted::ScriptedBackend;
+    use crate::constants::prompt::{EN, JA};
+
+    // 過去に試された・想定されるプロンプトインジェクションの例
+    const CORPUS: &str = include_str!("injection_corpus.txt");
+
+    const PROBLEM: &str = "ある男がバーに入ってきて、バーテンダーに水を一杯注文した。バーテンダーは銃を取り出し、男に狙いをつけて撃鉄を上げた。男は「ありがとう」と言って帰って行った。一体どういうことか？";
+    const SOLUTION: &str = "男はしゃっくりが止まらずに困っていて、水を飲んで止めようとしていた。バーテンダーはそれに気づいて、銃で驚かせることでしゃっくりを止めてあげた。男はしゃっくりが止まったので、感謝して水を飲まずに帰った。";
+
+    const PROBLEM_EN: &str = "A man walks into a bar and asks the bartender for a glass of water. The bartender pulls out a gun and points it at the man. The man says \"Thank you\" and leaves. Why?";
+    const SOLUTION_EN: &str = "The man had hiccups and wanted water to stop them. The bartender noticed and scared him with the gun, which cured his hiccups. The man no longer needed the water, thanked him and left.";
+
+    fn corpus() -> impl Iterator<Item = &'static str> {
+        CORPUS
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    }
+
+    #[test]
+    fn input_cannot_escape_delimiter() {
+        for input in corpus() {
+            let delimited = delimit_input(input);
+            assert!(delimited.starts_with(INPUT_OPEN), "{}", input);
+            assert!(delimited.ends_with(INPUT_CLOSE), "{}", input);
+            assert_eq!(delimited.matches(INPUT_OPEN).count(), 1, "{}", input);
+            assert_eq!(delimited.matches(INPUT_CLOSE).count(), 1, "{}", input);
+        }
+    }
+
+    #[test]
+    fn solution_is_only_in_system_message() {
+        for input in corpus() {
+            for kind in [Kind::Question, Kind::Answer, Kind::Hint] {
+                let messages = judge_request(&JA, PROBLEM, SOLUTION, kind, input);
+                assert_eq!(messages.len(), 2);
+                assert!(matches!(messages[0].role, Role::System));
+                assert!(messages[0].content.contains(SOLUTION));
+                assert!(matches!(messages[1].role, Role::User));
+                assert!(!messages[1].content.contains(SOLUTION));
+                assert!(messages[1].content.ends_with(INPUT_CLOSE), "{}", input);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn leaked_question_reply_is_replaced() {
+        // 判定役がインジェクションに従って解答を答えてしまった場合
+        let backend = ScriptedBackend::new(|_| format!("はい。解答は次のとおりです。{}", SOLUTION));
+
+        for input in corpus() {
+            let reply = judge(&backend, &JA, PROBLEM, SOLUTION, Kind::Question, input)
+                .await
+                .unwrap();
+            assert_eq!(reply, "はい。", "{}", input);
+        }
+        assert_eq!(backend.requests.lock().unwrap().len(), corpus().count());
+    }
+
+    #[tokio::test]
+    async fn paraphrased_leak_is_replaced() {
+        let backend = ScriptedBackend::new(|_| {
+            "No. He had hiccups and wanted to stop them with water; the bartender scared him \
+             with a gun, the hiccups were cured, and he thanked him and left."
+                .to_string()
+        });
+
+        for input in corpus() {
+            let reply = judge(
+                &backend,
+                &EN,
+                PROBLEM_EN,
+                SOLUTION_EN,
+                Kind::Question,
+                input,
+            )
+            .await
+            .unwrap();
+            assert_eq!(reply, "No.", "{}", input);
+        }
+
+        let backend = ScriptedBackend::new(|_| {
+            "いいえ。男はしゃっくりに困っていて、バーテンダーが銃で驚かせて止めてあげたので、感謝して帰りました。"
+                .to_string()
+        });
+        let reply = judge(
+            &backend,
+            &JA,
+            PROBLEM,
+            SOLUTION,
+            Kind::Question,
+            "ヒントをください",
+        )
+        .await
+        .unwrap();
+        assert_eq!(reply, "いいえ。");
+    }
+
+    #[tokio::test]
+    async fn leaked_answer_reply_is_replaced() {
+        let backend = ScriptedBackend::new(|_| format!("不正解です。正しくは、{}", SOLUTION));
+
+        for input in corpus() {
+            let reply = judge(&backend, &JA, PROBLEM, SOLUTION, Kind::Answer, input)
+                .await
+                .unwrap();
+            assert_eq!(reply, "不正解です。", "{}", input);
+        }
+    }
+
+    #[tokio::test]
+    async fn correct_reply_does_not_include_story() {
+        let backend = ScriptedBackend::new(|_| format!("正解です。{}", SOLUTION));
+
+        let reply = judge(
+            &backend,
+            &JA,
+            PROBLEM,
+            SOLUTION,
+            Kind::Answer,
+            "しゃっくりを止めるために銃で驚かせた",
+        )
+        .await
+        .unwrap();
+        assert_eq!(reply, "正解です。");
+    }
+
+    #[tokio::test]
+    async fn hints_are_kept() {
+        let hints = [
+            "はい。男は水を飲みたかったわけではないかもしれません。",
+            "いいえ、バーテンダーは怒っていません。",
+            "はい。バーテンダーは銃を取り出し、男に狙いをつけて撃鉄を上げました。",
+            "はい。男はしゃっくりをしていました。",
+            "関係ありません。",
+        ];
+
+        for hint in hints {
+            let backend = ScriptedBackend::new(move |_| hint.to_string());
+            let reply = judge(
+                &backend,
+                &JA,
+                PROBLEM,
+                SOLUTION,
+                Kind::Question,
+                "男は体調が悪かったですか？",
+            )
+            .await
+            .unwrap();
+            assert_eq!(reply, hint);
+        }
+    }
+}
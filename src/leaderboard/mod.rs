@@ -0,0 +1,409 @@
+// 正解の記録と、期間ごとのランキングの集計
+// 記録はチャンネルをまたいでサーバー全体で一つのファイルに保存する
+
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serenity::all::{ChannelId, UserId};
+use tokio::sync::Mutex;
+use tracing::error;
+
+use crate::storage::Store;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SolveRecord {
+    // UNIX時間（秒）
+    pub timestamp: i64,
+    pub channel_id: ChannelId,
+    // スレッド（チーム戦を含む）で正解した場合は親チャンネル
+    #[serde(default)]
+    pub parent_channel: Option<ChannelId>,
+    pub user_id: UserId,
+    pub user_name: String,
+    // 正解までに出た質問の数（参加者全員分）
+    pub questions: usize,
+}
+
+impl SolveRecord {
+    pub fn is_visible_from(&self, channel_id: ChannelId) -> bool {
+        self.channel_id == channel_id || self.parent_channel == Some(channel_id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    // 現在のセッション（/play してから、またはリセットしてから）
+    Session,
+    Week,
+    Month,
+    All,
+}
+
+impl Period {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "session" => Some(Period::Session),
+            "week" => Some(Period::Week),
+            "month" => Some(Period::Month),
+            "all" => Some(Period::All),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Period::Session => "session",
+            Period::Week => "week",
+            Period::Month => "month",
+            Period::All => "all",
+        }
+    }
+}
+
+// ランキングの1行分
+pub struct Standing {
+    pub user_name: String,
+    pub solves: usize,
+    pub average_questions: f64,
+    // 期間の終わり（集計中の期間は今日）まで毎日正解し続けている日数
+    // その日にまだ正解していなくても、前日まで続いていれば数える
+    pub streak: usize,
+}
+
+pub struct Leaderboard {
+    records: Mutex<Vec<SolveRecord>>,
+    store: Store,
+    // 週・月の区切りと連続日数の計算に使うタイムゾーン
+    offset: FixedOffset,
+}
+
+impl Leaderboard {
+    pub fn new(store: Store, offset: FixedOffset) -> Self {
+        Self {
+            records: Mutex::new(store.load("solves")),
+            store,
+            offset,
+        }
+    }
+
+    pub async fn record(
+        &self,
+        channel_id: ChannelId,
+        parent_channel: Option<ChannelId>,
+        user_id: UserId,
+        user_name: String,
+        questions: usize,
+    ) {
+        let mut records = self.records.lock().await;
+        records.push(SolveRecord {
+            timestamp: Utc::now().timestamp(),
+            channel_id,
+            parent_channel,
+            user_id,
+            user_name,
+            questions,
+        });
+
+        if let Err(e) = self.store.save("solves", &*records) {
+            error!("正解の記録の保存に失敗しました: {:?}", e);
+        }
+    }
+
+    // 期間の開始時刻。セッションの場合は開始時刻を渡す
+    pub fn period_start(&self, period: Period, session_started: i64) -> i64 {
+        self.period_start_at(period, session_started, Utc::now())
+    }
+
+    fn period_start_at(&self, period: Period, session_started: i64, now: DateTime<Utc>) -> i64 {
+        let today = now.with_timezone(&self.offset).date_naive();
+        let start = match period {
+            Period::Session => return session_started,
+            Period::All => return i64::MIN,
+            Period::Week => today - Duration::days(today.weekday().num_days_from_monday() as i64),
+            Period::Month => today.with_day(1).unwrap_or(today),
+        };
+        self.start_of_day(start).timestamp()
+    }
+
+    // 次の月曜日の0時
+    pub fn next_week_start(&self) -> DateTime<FixedOffset> {
+        self.next_week_start_at(Utc::now())
+    }
+
+    fn next_week_start_at(&self, now: DateTime<Utc>) -> DateTime<FixedOffset> {
+        let today = now.with_timezone(&self.offset).date_naive();
+        let days = 7 - today.weekday().num_days_from_monday() as i64;
+        self.start_of_day(today + Duration::days(days))
+    }
+
+    // 指定した期間の正解数が多い順のランキング
+    // セッションの場合はそのチャンネルと、そのチャンネルから作ったスレッドの記録だけを集計する
+    pub async fn standings(
+        &self,
+        from: i64,
+        until: i64,
+        channel_id: Option<ChannelId>,
+    ) -> Vec<Standing> {
+        self.standings_at(from, until, channel_id, Utc::now()).await
+    }
+
+    async fn standings_at(
+        &self,
+        from: i64,
+        until: i64,
+        channel_id: Option<ChannelId>,
+        now: DateTime<Utc>,
+    ) -> Vec<Standing> {
+        // 連続日数は期間の最終日（集計中の期間は今日）までで数える
+        let last_day = DateTime::from_timestamp(until.saturating_sub(1).min(now.timestamp()), 0)
+            .unwrap_or(now)
+            .with_timezone(&self.offset)
+            .date_naive();
+
+        // 期間内の記録と、連続日数に使う期間より前も含めた正解した日
+        let records = self.records.lock().await;
+        let mut by_user: HashMap<UserId, (Vec<&SolveRecord>, BTreeSet<NaiveDate>)> = HashMap::new();
+        for record in records.iter().filter(|record| {
+            record.timestamp < until
+                && channel_id.is_none_or(|channel_id| record.is_visible_from(channel_id))
+        }) {
+            let (in_period, days) = by_user.entry(record.user_id).or_default();
+            if let Some(time) = DateTime::from_timestamp(record.timestamp, 0) {
+                days.insert(time.with_timezone(&self.offset).date_naive());
+            }
+            if record.timestamp >= from {
+                in_period.push(record);
+            }
+        }
+
+        let mut standings: Vec<_> = by_user
+            .into_values()
+            .filter(|(records, _)| !records.is_empty())
+            .map(|(records, days)| {
+                let questions: usize = records.iter().map(|record| record.questions).sum();
+
+                Standing {
+                    // 最後に正解したときの名前を使う
+                    user_name: records
+                        .last()
+                        .map(|record| record.user_name.clone())
+                        .unwrap_or_default(),
+                    solves: records.len(),
+                    average_questions: questions as f64 / records.len() as f64,
+                    streak: current_streak(&days, last_day),
+                }
+            })
+            .collect();
+
+        standings.sort_by(|a, b| {
+            b.solves
+                .cmp(&a.solves)
+                .then(a.average_questions.total_cmp(&b.average_questions))
+                .then(a.user_name.cmp(&b.user_name))
+        });
+        standings
+    }
+
+    fn start_of_day(&self, date: NaiveDate) -> DateTime<FixedOffset> {
+        self.offset
+            .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap_or_default())
+            .single()
+            .unwrap_or_else(|| Utc::now().with_timezone(&self.offset))
+    }
+}
+
+// last_day か、その前日から遡って毎日正解している日数
+fn current_streak(days: &BTreeSet<NaiveDate>, last_day: NaiveDate) -> usize {
+    let mut day = if days.contains(&last_day) {
+        last_day
+    } else {
+        last_day - Duration::days(1)
+    };
+    let mut streak = 0;
+    while days.contains(&day) {
+        streak += 1;
+        day -= Duration::days(1);
+    }
+    streak
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaderboard(offset_hours: i32) -> Leaderboard {
+        let dir = std::env::temp_dir().join(format!("leaderboard-{}", std::process::id()));
+        Leaderboard::new(
+            Store::new(dir),
+            FixedOffset::east_opt(offset_hours * 3600).unwrap(),
+        )
+    }
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text).unwrap().to_utc()
+    }
+
+    fn record(user_id: u64, time: &str) -> SolveRecord {
+        SolveRecord {
+            timestamp: utc(time).timestamp(),
+            channel_id: ChannelId::new(1),
+            parent_channel: None,
+            user_id: UserId::new(user_id),
+            user_name: format!("user{}", user_id),
+            questions: 4,
+        }
+    }
+
+    fn days(dates: &[&str]) -> BTreeSet<NaiveDate> {
+        dates.iter().map(|date| date.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn week_starts_on_monday_in_the_configured_timezone() {
+        // UTCではまだ日曜日だが、日本時間ではすでに月曜日の1時
+        let now = utc("2026-10-18T16:00:00Z");
+        assert_eq!(
+            leaderboard(9).period_start_at(Period::Week, 0, now),
+            utc("2026-10-18T15:00:00Z").timestamp()
+        );
+        assert_eq!(
+            leaderboard(0).period_start_at(Period::Week, 0, now),
+            utc("2026-10-12T00:00:00Z").timestamp()
+        );
+        assert_eq!(
+            leaderboard(9).next_week_start_at(now).to_utc(),
+            utc("2026-10-25T15:00:00Z")
+        );
+        assert_eq!(
+            leaderboard(0).next_week_start_at(now).to_utc(),
+            utc("2026-10-19T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn month_starts_on_the_first_in_the_configured_timezone() {
+        let now = utc("2026-10-31T20:00:00Z");
+        assert_eq!(
+            leaderboard(9).period_start_at(Period::Month, 0, now),
+            utc("2026-10-31T15:00:00Z").timestamp()
+        );
+        assert_eq!(
+            leaderboard(0).period_start_at(Period::Month, 0, now),
+            utc("2026-10-01T00:00:00Z").timestamp()
+        );
+        assert_eq!(leaderboard(9).period_start_at(Period::Session, 42, now), 42);
+        assert_eq!(
+            leaderboard(9).period_start_at(Period::All, 42, now),
+            i64::MIN
+        );
+    }
+
+    fn date(text: &str) -> NaiveDate {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn streak_counts_back_from_today_or_yesterday() {
+        let today = date("2026-10-06");
+        assert_eq!(current_streak(&days(&[]), today), 0);
+        assert_eq!(current_streak(&days(&["2026-10-06"]), today), 1);
+        // 以前の長い連続は数えず、1日空いた後の連続だけを数える
+        let solved = days(&[
+            "2026-10-01",
+            "2026-10-02",
+            "2026-10-03",
+            "2026-10-05",
+            "2026-10-06",
+        ]);
+        assert_eq!(current_streak(&solved, today), 2);
+        // 今日まだ正解していなくても前日までの連続は続いている
+        assert_eq!(current_streak(&solved, date("2026-10-07")), 2);
+        // 2日空くと途切れる
+        assert_eq!(current_streak(&solved, date("2026-10-08")), 0);
+        // 月をまたいでも続く
+        assert_eq!(
+            current_streak(
+                &days(&["2026-09-30", "2026-10-01", "2026-10-03"]),
+                date("2026-10-01")
+            ),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn standings_count_days_and_periods_in_the_configured_timezone() {
+        let leaderboard = leaderboard(9);
+        *leaderboard.records.lock().await = vec![
+            // 期間より前の記録は正解数に入らないが、連続日数には数える
+            record(1, "2026-09-30T16:00:00Z"),
+            // 日本時間では10月2日から4日で、期間より前と合わせて4日続いている
+            record(1, "2026-10-01T16:00:00Z"),
+            record(1, "2026-10-02T16:00:00Z"),
+            record(1, "2026-10-03T16:00:00Z"),
+            // 日本時間では同じ10月5日。今日（6日）はまだ正解していない
+            record(2, "2026-10-04T16:00:00Z"),
+            record(2, "2026-10-05T14:00:00Z"),
+            // 期間の終わりちょうどの記録は含まない
+            record(2, "2026-10-06T00:00:00Z"),
+        ];
+
+        let now = utc("2026-10-06T03:00:00Z");
+        let standings = leaderboard
+            .standings_at(
+                utc("2026-10-01T00:00:00Z").timestamp(),
+                utc("2026-10-06T00:00:00Z").timestamp(),
+                None,
+                now,
+            )
+            .await;
+        assert_eq!(standings.len(), 2);
+        assert_eq!(standings[0].user_name, "user1");
+        assert_eq!(standings[0].solves, 3);
+        // 10月4日で止まっていて、期間の最終日の6日には途切れている
+        assert_eq!(standings[0].streak, 0);
+        assert_eq!(standings[1].user_name, "user2");
+        assert_eq!(standings[1].solves, 2);
+        assert_eq!(standings[1].streak, 1);
+
+        // 集計中の期間では今日（5日）までで数え、前日まで正解していれば続いている
+        let standings = leaderboard
+            .standings_at(
+                utc("2026-10-01T00:00:00Z").timestamp(),
+                i64::MAX,
+                None,
+                utc("2026-10-05T03:00:00Z"),
+            )
+            .await;
+        let user1 = standings
+            .iter()
+            .find(|standing| standing.user_name == "user1")
+            .unwrap();
+        assert_eq!(user1.streak, 4);
+    }
+
+    #[tokio::test]
+    async fn session_standings_include_team_threads() {
+        let leaderboard = leaderboard(9);
+        let parent = ChannelId::new(1);
+        let thread = SolveRecord {
+            channel_id: ChannelId::new(2),
+            parent_channel: Some(parent),
+            ..record(1, "2026-10-05T00:00:00Z")
+        };
+        let other = SolveRecord {
+            channel_id: ChannelId::new(3),
+            ..record(2, "2026-10-05T00:00:00Z")
+        };
+        *leaderboard.records.lock().await = vec![thread, other];
+
+        let standings = leaderboard.standings(0, i64::MAX, Some(parent)).await;
+        assert_eq!(standings.len(), 1);
+        assert_eq!(standings[0].user_name, "user1");
+        // スレッドの中で集計しても数える
+        let standings = leaderboard
+            .standings(0, i64::MAX, Some(ChannelId::new(2)))
+            .await;
+        assert_eq!(standings.len(), 1);
+    }
+}
@@ -0,0 +1,21 @@
+// Discordのボット（src/main.rs）とターミナル版（src/bin/situation-puzzle-cli.rs）で共有するモジュール
+
+pub mod api;
+pub mod config;
+pub mod constants;
+pub mod critique;
+pub mod daily;
+pub mod game;
+pub mod handlers;
+pub mod i18n;
+pub mod judge;
+pub mod leaderboard;
+pub mod library;
+pub mod models;
+pub mod moderation;
+pub mod pause;
+pub mod scoring;
+pub mod storage;
+pub mod submissions;
+pub mod transcript;
+pub mod utils;
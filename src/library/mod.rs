@@ -0,0 +1,645 @@
+// 生成した問題のライブラリ
+// 問題と解答、やりとりの記録、プレイ回数、プレイヤーの評価をまとめて保存し、評価の高い問題を探せるようにする
+// 生成した問題はほぼ同じ文面の問題がすでにあれば新しく追加せず、既存の問題として扱う
+// プレイヤーが投稿して承認された問題も、作者の名前とヒント付きで同じように保存する
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use serenity::all::{ChannelId, UserId};
+use tokio::sync::Mutex;
+use tracing::error;
+
+use crate::constants::prompt::game_prompt;
+use crate::game::{Game, QaEntry};
+use crate::i18n::Locale;
+use crate::models::{Difficulty, Session};
+use crate::storage::Store;
+use crate::submissions::Submission;
+
+// 1つの問題について保存しておくやりとりの記録の数
+const TRANSCRIPT_LIMIT: usize = 5;
+// 再利用する問題の平均評価の下限
+const MIN_STARS: f64 = 2.5;
+// 問題文の文字n-gramの集合がこの割合以上重なっていたら同じ問題とみなす
+const DUPLICATE_SIMILARITY: f64 = 0.6;
+// 問題文の example_shingles のうちこの割合がプロンプトの例題のどれかに含まれていたら、例題をなぞった問題とみなす
+// 組み込みのプロンプトの例題に対して、言い換えた例題は0.36以上、無関係な問題は0.2以下になる（テストの例文で測定）
+// 例題と場面だけが似た問題は0.24〜0.38で、境界に近い
+const EXAMPLE_CONTAINMENT: f64 = 0.3;
+
+// 星の数以外の評価
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Feedback {
+    // 答えにたどり着けない
+    Unfair,
+    TooEasy,
+    // ストーリーが腑に落ちない
+    Nonsense,
+}
+
+impl Feedback {
+    pub const ALL: [Feedback; 3] = [Feedback::Unfair, Feedback::TooEasy, Feedback::Nonsense];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "unfair" => Some(Feedback::Unfair),
+            "easy" => Some(Feedback::TooEasy),
+            "nonsense" => Some(Feedback::Nonsense),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Feedback::Unfair => "unfair",
+            Feedback::TooEasy => "easy",
+            Feedback::Nonsense => "nonsense",
+        }
+    }
+}
+
+// ユーザーごとの評価。星は付け直すと上書きし、その他の評価は重複しないように追加する
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Rating {
+    pub user_id: UserId,
+    pub stars: Option<u8>,
+    #[serde(default)]
+    pub feedback: Vec<Feedback>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LibraryPuzzle {
+    pub id: u64,
+    pub locale: Locale,
+    pub difficulty: Difficulty,
+    pub problem: String,
+    pub solution: String,
+    #[serde(default)]
+    pub plays: u32,
+    #[serde(default)]
+    pub solves: u32,
+    // 出題したチャンネル。再利用するときに同じチャンネルで出題しないようにする
+    #[serde(default)]
+    pub channels: Vec<ChannelId>,
+    #[serde(default)]
+    pub transcripts: Vec<Vec<QaEntry>>,
+    #[serde(default)]
+    pub ratings: Vec<Rating>,
+    // 投稿された問題の場合はタイトルと作者の名前、作者が用意したヒント
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub hints: Vec<String>,
+}
+
+impl LibraryPuzzle {
+    pub fn solve_rate(&self) -> Option<f64> {
+        (self.plays > 0).then(|| self.solves as f64 / self.plays as f64)
+    }
+
+    pub fn average_stars(&self) -> Option<f64> {
+        let stars: Vec<f64> = self
+            .ratings
+            .iter()
+            .filter_map(|rating| rating.stars)
+            .map(f64::from)
+            .collect();
+        (!stars.is_empty()).then(|| stars.iter().sum::<f64>() / stars.len() as f64)
+    }
+
+    // 評価の低い問題と、理不尽・ストーリーが腑に落ちないという評価が半数以上の問題は再利用しない
+    pub fn is_reusable(&self) -> bool {
+        let complaints = self
+            .ratings
+            .iter()
+            .filter(|rating| {
+                rating
+                    .feedback
+                    .iter()
+                    .any(|feedback| matches!(feedback, Feedback::Unfair | Feedback::Nonsense))
+            })
+            .count();
+
+        self.average_stars().is_none_or(|stars| stars >= MIN_STARS)
+            && complaints * 2 < self.ratings.len().max(1)
+    }
+}
+
+pub struct Library {
+    puzzles: Mutex<Vec<LibraryPuzzle>>,
+    store: Store,
+}
+
+impl Library {
+    pub fn new(store: Store) -> Self {
+        Self {
+            puzzles: Mutex::new(store.load("library")),
+            store,
+        }
+    }
+
+    // 生成した問題を追加し、問題のIDをゲームに設定する
+    // ほぼ同じ問題がすでにある場合はその問題のIDを使う
+    pub async fn add_generated(
+        &self,
+        game: &mut Game,
+        difficulty: Difficulty,
+        channel_id: ChannelId,
+    ) -> u64 {
+        let mut puzzles = self.puzzles.lock().await;
+        let index = match find_duplicate(&puzzles, &game.problem, game.locale) {
+            Some(index) => index,
+            None => push_game(&mut puzzles, game, difficulty),
+        };
+
+        let puzzle = &mut puzzles[index];
+        if !puzzle.channels.contains(&channel_id) {
+            puzzle.channels.push(channel_id);
+        }
+        let id = puzzle.id;
+        game.puzzle_id = Some(id);
+
+        self.save(&puzzles);
+        id
+    }
+
+    // 承認された投稿を追加し、問題のIDを返す
+    pub async fn add_submission(&self, submission: &Submission) -> u64 {
+        let mut puzzles = self.puzzles.lock().await;
+        let id = next_id(&puzzles);
+        puzzles.push(LibraryPuzzle {
+            id,
+            locale: submission.locale,
+            difficulty: submission.difficulty,
+            problem: submission.problem.clone(),
+            solution: submission.solution.clone(),
+            plays: 0,
+            solves: 0,
+            channels: vec![],
+            transcripts: vec![],
+            ratings: vec![],
+            title: Some(submission.title.clone()),
+            author: Some(submission.author_name.clone()),
+            hints: submission.hints.clone(),
+        });
+
+        self.save(&puzzles);
+        id
+    }
+
+    // このチャンネルで出題済みの問題か、システムプロンプトの例題とほぼ同じ問題かどうか
+    // 他のチャンネルで出題した問題は重複とせず、add_generated で同じ問題としてまとめる
+    pub async fn is_duplicate(
+        &self,
+        problem: &str,
+        locale: Locale,
+        system_prompt: &str,
+        channel_id: ChannelId,
+    ) -> bool {
+        if copies_example(problem, locale, system_prompt) {
+            return true;
+        }
+        let puzzles = self.puzzles.lock().await;
+        let played: Vec<_> = puzzles
+            .iter()
+            .filter(|puzzle| puzzle.channels.contains(&channel_id))
+            .cloned()
+            .collect();
+        find_duplicate(&played, problem, locale).is_some()
+    }
+
+    // 再利用できる問題のうち、このチャンネルでまだ出題しておらず評価の高いものを選ぶ
+    // 出題できなかった場合に次も選べるよう、出題済みにするのは mark_played で行う
+    pub async fn pick(
+        &self,
+        channel_id: ChannelId,
+        locale: Locale,
+        difficulty: Difficulty,
+    ) -> Option<LibraryPuzzle> {
+        let puzzles = self.puzzles.lock().await;
+        puzzles
+            .iter()
+            .filter(|puzzle| {
+                puzzle.locale == locale
+                    && puzzle.difficulty == difficulty
+                    && !puzzle.channels.contains(&channel_id)
+                    && puzzle.is_reusable()
+            })
+            .max_by(|a, b| {
+                a.average_stars()
+                    .unwrap_or(MIN_STARS)
+                    .total_cmp(&b.average_stars().unwrap_or(MIN_STARS))
+                    .then(a.plays.cmp(&b.plays))
+            })
+            .cloned()
+    }
+
+    // チャンネルで出題した問題として記録する。出題を始めてから呼ぶ
+    pub async fn mark_played(&self, id: u64, channel_id: ChannelId) {
+        let mut puzzles = self.puzzles.lock().await;
+        let Some(puzzle) = puzzles.iter_mut().find(|puzzle| puzzle.id == id) else {
+            return;
+        };
+        if puzzle.channels.contains(&channel_id) {
+            return;
+        }
+        puzzle.channels.push(channel_id);
+        self.save(&puzzles);
+    }
+
+    // 終わった問題のプレイ回数・正解数とやりとりを記録し、評価ボタンに使う問題のIDを返す
+    // ライブラリにない問題（生成した時点で保存できなかったもの）はここで追加する
+    // 記録しない問題の場合はNone
+    pub async fn record_game(&self, session: &mut Session, solved: bool) -> Option<u64> {
+        if session.game.unlisted {
+            return None;
+        }
+        let mut puzzles = self.puzzles.lock().await;
+        let index = match session
+            .game
+            .puzzle_id
+            .and_then(|id| puzzles.iter().position(|puzzle| puzzle.id == id))
+        {
+            Some(index) => index,
+            None => push_game(&mut puzzles, &session.game, session.settings.difficulty),
+        };
+
+        let puzzle = &mut puzzles[index];
+        puzzle.plays += 1;
+        if solved {
+            puzzle.solves += 1;
+        }
+        puzzle.transcripts.push(session.game.qa_log.clone());
+        if puzzle.transcripts.len() > TRANSCRIPT_LIMIT {
+            puzzle.transcripts.remove(0);
+        }
+        let id = puzzle.id;
+        session.game.puzzle_id = Some(id);
+
+        self.save(&puzzles);
+        Some(id)
+    }
+
+    // 星（1〜5）かその他の評価を記録する。問題が見つからない場合はfalse
+    pub async fn rate(
+        &self,
+        id: u64,
+        user_id: UserId,
+        stars: Option<u8>,
+        feedback: Option<Feedback>,
+    ) -> bool {
+        let mut puzzles = self.puzzles.lock().await;
+        let Some(puzzle) = puzzles.iter_mut().find(|puzzle| puzzle.id == id) else {
+            return false;
+        };
+
+        let rating = match puzzle
+            .ratings
+            .iter()
+            .position(|rating| rating.user_id == user_id)
+        {
+            Some(index) => &mut puzzle.ratings[index],
+            None => {
+                puzzle.ratings.push(Rating {
+                    user_id,
+                    stars: None,
+                    feedback: vec![],
+                });
+                puzzle.ratings.last_mut().unwrap()
+            }
+        };
+        if let Some(stars) = stars {
+            rating.stars = Some(stars.clamp(1, 5));
+        }
+        if let Some(feedback) = feedback.filter(|feedback| !rating.feedback.contains(feedback)) {
+            rating.feedback.push(feedback);
+        }
+
+        self.save(&puzzles);
+        true
+    }
+
+    // 再利用できる問題を平均評価の高い順に並べる
+    pub async fn top_rated(&self, limit: usize) -> Vec<LibraryPuzzle> {
+        let puzzles = self.puzzles.lock().await;
+        let mut rated: Vec<_> = puzzles
+            .iter()
+            .filter(|puzzle| puzzle.average_stars().is_some() && puzzle.is_reusable())
+            .cloned()
+            .collect();
+
+        rated.sort_by(|a, b| {
+            b.average_stars()
+                .unwrap_or_default()
+                .total_cmp(&a.average_stars().unwrap_or_default())
+                .then(b.ratings.len().cmp(&a.ratings.len()))
+        });
+        rated.truncate(limit);
+        rated
+    }
+
+    fn save(&self, puzzles: &[LibraryPuzzle]) {
+        if let Err(e) = self.store.save("library", &puzzles) {
+            error!("問題のライブラリの保存に失敗しました: {:?}", e);
+        }
+    }
+}
+
+// 同じ言語でほぼ同じ問題文の問題の位置
+fn find_duplicate(puzzles: &[LibraryPuzzle], problem: &str, locale: Locale) -> Option<usize> {
+    let shingles = shingles(problem, locale);
+    puzzles.iter().position(|puzzle| {
+        puzzle.locale == locale
+            && similarity(&shingles, &self::shingles(&puzzle.problem, locale))
+                >= DUPLICATE_SIMILARITY
+    })
+}
+
+// システムプロンプトの例題をなぞった問題かどうか
+// 例題以外の説明文と比べると共通する言い回しが多いため、例題の問題文とだけ比べる
+fn copies_example(problem: &str, locale: Locale, system_prompt: &str) -> bool {
+    example_containment(problem, locale, system_prompt) >= EXAMPLE_CONTAINMENT
+}
+
+// 問題文の example_shingles のうち、いずれかの例題に含まれる割合の最大値
+fn example_containment(problem: &str, locale: Locale, system_prompt: &str) -> f64 {
+    let shingles = example_shingles(problem, locale);
+    if shingles.is_empty() {
+        return 0.0;
+    }
+    example_puzzles(system_prompt, locale)
+        .map(|example| {
+            let example = example_shingles(example, locale);
+            shingles.intersection(&example).count() as f64 / shingles.len() as f64
+        })
+        .fold(0.0, f64::max)
+}
+
+// 例題との比較に使う文字n-gramの集合
+// 日本語はひらがな（助詞や「なぜでしょうか」など）が共通しやすいため除き、漢字・カタカナの2文字で比べる
+fn example_shingles(text: &str, locale: Locale) -> HashSet<String> {
+    match locale {
+        Locale::Ja => {
+            let chars: Vec<char> = text
+                .chars()
+                .filter(|c| c.is_alphanumeric() && !('\u{3040}'..='\u{309f}').contains(c))
+                .flat_map(char::to_lowercase)
+                .collect();
+            chars
+                .windows(2)
+                .map(|window| window.iter().collect())
+                .collect()
+        }
+        Locale::En => shingles(text, locale),
+    }
+}
+
+// システムプロンプトの例題の問題文（問題文の見出しで始まる行）
+fn example_puzzles(system_prompt: &str, locale: Locale) -> impl Iterator<Item = &str> {
+    let marker = game_prompt(locale).problem_marker;
+    system_prompt
+        .lines()
+        .filter_map(move |line| line.trim().strip_prefix(marker))
+}
+
+// セッションの問題を新しいIDで追加し、その位置を返す
+fn push_game(puzzles: &mut Vec<LibraryPuzzle>, game: &Game, difficulty: Difficulty) -> usize {
+    let id = next_id(puzzles);
+    puzzles.push(LibraryPuzzle {
+        id,
+        locale: game.locale,
+        difficulty,
+        problem: game.problem.clone(),
+        solution: game.solution.clone(),
+        plays: 0,
+        solves: 0,
+        channels: vec![],
+        transcripts: vec![],
+        ratings: vec![],
+        title: None,
+        author: None,
+        hints: vec![],
+    });
+    puzzles.len() - 1
+}
+
+fn next_id(puzzles: &[LibraryPuzzle]) -> u64 {
+    puzzles.iter().map(|puzzle| puzzle.id).max().unwrap_or(0) + 1
+}
+
+// 空白や記号を除いて小文字にした文字n-gramの集合
+// 英語は3文字だと共通する綴りが多すぎるため長くする
+fn shingles(text: &str, locale: Locale) -> HashSet<String> {
+    let size = match locale {
+        Locale::Ja => 3,
+        Locale::En => 5,
+    };
+    let chars: Vec<char> = text
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect();
+    chars
+        .windows(size)
+        .map(|window| window.iter().collect())
+        .collect()
+}
+
+// Jaccard係数
+fn similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn puzzle(id: u64, locale: Locale, problem: &str) -> LibraryPuzzle {
+        LibraryPuzzle {
+            id,
+            locale,
+            difficulty: Difficulty::Normal,
+            problem: problem.to_string(),
+            solution: String::new(),
+            plays: 0,
+            solves: 0,
+            channels: vec![],
+            transcripts: vec![],
+            ratings: vec![],
+            title: None,
+            author: None,
+            hints: vec![],
+        }
+    }
+
+    fn similarity_of(a: &str, b: &str, locale: Locale) -> f64 {
+        similarity(&shingles(a, locale), &shingles(b, locale))
+    }
+
+    #[test]
+    fn reworded_problem_is_similar() {
+        let ja = similarity_of(
+            "ある男がバーでバーテンダーに水を一杯注文した。バーテンダーは銃を取り出して男に向けた。",
+            "ある男がバーでバーテンダーに水を一杯注文した。するとバーテンダーは銃を取り出して男に向けた。",
+            Locale::Ja,
+        );
+        assert!(ja >= DUPLICATE_SIMILARITY, "{ja}");
+        let en = similarity_of(
+            "A man walks into a bar and asks the bartender for a glass of water.",
+            "A man walked into a bar and asked the bartender for a glass of water!",
+            Locale::En,
+        );
+        assert!(en >= DUPLICATE_SIMILARITY, "{en}");
+    }
+
+    #[test]
+    fn unrelated_problem_is_not_similar() {
+        let ja = similarity_of(
+            "ある女性が毎朝同じ時間に駅へ行くが、電車には一度も乗らない。なぜでしょうか？",
+            "ある男性がレストランでスープを注文した。一口飲んだ男性は店を出て行った。なぜでしょうか？",
+            Locale::Ja,
+        );
+        assert!(ja < DUPLICATE_SIMILARITY, "{ja}");
+        assert_eq!(similarity_of("", "", Locale::En), 0.0);
+    }
+
+    #[test]
+    fn find_duplicate_matches_only_the_same_locale() {
+        let problem = "A man walks into a bar and asks the bartender for a glass of water.";
+        let puzzles = vec![
+            puzzle(
+                1,
+                Locale::En,
+                "A woman goes to the station every morning but never boards a train.",
+            ),
+            puzzle(2, Locale::Ja, problem),
+            puzzle(3, Locale::En, problem),
+        ];
+        assert_eq!(find_duplicate(&puzzles, problem, Locale::En), Some(2));
+        assert_eq!(
+            find_duplicate(&puzzles, "A boy came home soaking wet.", Locale::En),
+            None
+        );
+    }
+
+    // 例題を言い換えた問題
+    const REWORDED: [(Locale, &str); 7] = [
+        (Locale::Ja, "男がバーで水を頼むと、バーテンダーは彼に銃を向けた。男はお礼を言って店を出た。なぜか？"),
+        (Locale::Ja, "ある男がバーに来て水を一杯頼んだ。するとバーテンダーが銃を突きつけた。男は感謝して帰った。なぜ？"),
+        (Locale::Ja, "海岸で釣りをしていた男性が大きな波にさらわれ、道具も魚も失った。それでも男性は驚かなかった。なぜか？"),
+        (Locale::Ja, "釣りをしていた男性が波にさらわれて道具を失ったのに、驚かなかった。なぜでしょう？"),
+        (Locale::En, "A man asks a bartender for water. The bartender aims a gun at him, and the man thanks him and leaves. Why?"),
+        (Locale::En, "A guy enters a bar and orders water. The bartender pulls a gun on him. He says thanks and walks out. Why?"),
+        (Locale::En, "A man fishing on the beach was swept away by a big wave and lost his gear and fish, but he wasn't surprised. Why?"),
+    ];
+
+    // 例題と無関係な問題
+    const UNRELATED: [(Locale, &str); 6] = [
+        (Locale::Ja, "ある女性が毎朝同じ時間に駅へ行くが、電車には一度も乗らない。なぜでしょうか？"),
+        (Locale::Ja, "ある男性がレストランでアホウドリのスープを注文した。一口飲んだ男性は店を出て、自殺してしまった。一体なぜでしょうか？"),
+        (Locale::Ja, "ある少年が雨の日に傘をさして学校へ行ったが、家に帰るとずぶ濡れだった。しかし母親は喜んだ。なぜか？"),
+        (Locale::En, "A woman goes to the train station every morning but never boards a train. Why?"),
+        (Locale::En, "A man orders albatross soup at a restaurant. After one sip he leaves and takes his own life. Why?"),
+        (Locale::En, "A boy walked home in the rain with an umbrella, but arrived soaking wet. His mother was delighted. Why?"),
+    ];
+
+    fn containment(locale: Locale, problem: &str) -> f64 {
+        example_containment(problem, locale, game_prompt(locale).system)
+    }
+
+    #[test]
+    fn threshold_separates_reworded_and_unrelated_examples() {
+        let reworded = REWORDED
+            .iter()
+            .map(|&(locale, problem)| containment(locale, problem))
+            .fold(f64::MAX, f64::min);
+        let unrelated = UNRELATED
+            .iter()
+            .map(|&(locale, problem)| containment(locale, problem))
+            .fold(0.0, f64::max);
+        // しきい値の前後にそれぞれ0.05以上の余裕がある
+        assert!(reworded >= EXAMPLE_CONTAINMENT + 0.05, "{reworded}");
+        assert!(unrelated <= EXAMPLE_CONTAINMENT - 0.05, "{unrelated}");
+    }
+
+    #[test]
+    fn borderline_problems_stay_on_their_side() {
+        // バーという場面だけが例題と同じ問題
+        let bar = containment(
+            Locale::Ja,
+            "ある男がバーで友人を待っていたが、友人は来なかった。男は笑って帰った。なぜか？",
+        );
+        assert!(bar < EXAMPLE_CONTAINMENT, "{bar}");
+        let cramp = containment(
+            Locale::En,
+            "A man was swimming in the sea when he suddenly got a cramp. He survived. Why?",
+        );
+        assert!(cramp < EXAMPLE_CONTAINMENT, "{cramp}");
+        // 海で男性に突然なにかが起きるという流れまで同じ問題は、例題をなぞったものとみなす
+        let wave = containment(
+            Locale::Ja,
+            "ある男性が海で泳いでいると、突然足がつった。男性は助かった。なぜでしょうか？",
+        );
+        assert!(wave >= EXAMPLE_CONTAINMENT, "{wave}");
+    }
+
+    #[tokio::test]
+    async fn duplicates_are_checked_against_the_channel_history() {
+        let dir = std::env::temp_dir().join(format!("library-{}", std::process::id()));
+        let library = Library::new(Store::new(dir));
+        let problem =
+            "ある女性が毎朝同じ時間に駅へ行くが、電車には一度も乗らない。なぜでしょうか？";
+        let played = ChannelId::new(1);
+        let other = ChannelId::new(2);
+        *library.puzzles.lock().await = vec![LibraryPuzzle {
+            channels: vec![played],
+            ..puzzle(1, Locale::Ja, problem)
+        }];
+
+        let prompt = game_prompt(Locale::Ja).system;
+        assert!(
+            library
+                .is_duplicate(problem, Locale::Ja, prompt, played)
+                .await
+        );
+        assert!(
+            !library
+                .is_duplicate(problem, Locale::Ja, prompt, other)
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn picked_puzzle_is_played_only_once_marked() {
+        let dir = std::env::temp_dir().join(format!("library-pick-{}", std::process::id()));
+        let library = Library::new(Store::new(dir));
+        let channel_id = ChannelId::new(1);
+        *library.puzzles.lock().await = vec![puzzle(1, Locale::Ja, "問題")];
+
+        // 出題できなかった場合に備えて、選んだだけでは出題済みにしない
+        let picked = library
+            .pick(channel_id, Locale::Ja, Difficulty::Normal)
+            .await;
+        assert_eq!(picked.map(|puzzle| puzzle.id), Some(1));
+        assert!(library
+            .pick(channel_id, Locale::Ja, Difficulty::Normal)
+            .await
+            .is_some());
+
+        library.mark_played(1, channel_id).await;
+        library.mark_played(1, channel_id).await;
+        assert_eq!(library.puzzles.lock().await[0].channels, vec![channel_id]);
+        assert!(library
+            .pick(channel_id, Locale::Ja, Difficulty::Normal)
+            .await
+            .is_none());
+    }
+}
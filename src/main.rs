@@ -1,16 +1,10 @@
-mod api;
-mod config;
-mod constants;
-mod handlers;
-mod models;
-mod utils;
+use std::sync::Arc;
 
 use serenity::prelude::*;
 use shuttle_runtime::SecretStore;
 
-use crate::config::Config;
-
-use crate::handlers::Bot;
+use situation_puzzle::config::Config;
+use situation_puzzle::handlers::{Bot, BotKey};
 
 #[shuttle_runtime::main]
 async fn serenity(
@@ -19,10 +13,14 @@ async fn serenity(
     let config = Config::from_secrets(&secrets).await;
 
     // Set gateway intents, which decides what events the bot will be notified about
-    let intents = GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT;
+    let intents = GatewayIntents::GUILD_MESSAGES
+        | GatewayIntents::DIRECT_MESSAGES
+        | GatewayIntents::MESSAGE_CONTENT;
 
+    let bot = Arc::new(Bot::new(&config));
     let client = Client::builder(&config.discord_token, intents)
-        .event_handler(Bot::new(config.discord_guild_id, config.openai_api_key))
+        .event_handler_arc(bot.clone())
+        .type_map_insert::<BotKey>(bot)
         .await
         .expect("Err creating client");
 
@@ -0,0 +1,63 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use serenity::all::ChannelId;
+
+use crate::i18n::Locale;
+use crate::models::DisplayMode;
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "easy" => Some(Difficulty::Easy),
+            "normal" => Some(Difficulty::Normal),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        }
+    }
+}
+
+// チャンネルごとのゲームのデフォルト設定
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ChannelSettings {
+    pub difficulty: Difficulty,
+    pub theme: Option<String>,
+    // 指定がない場合は/playを実行したユーザーの言語
+    pub locale: Option<Locale>,
+    // 制限時間（分）。時間切れになるとギブアップ扱いになる
+    pub time_limit: Option<u64>,
+    pub display: DisplayMode,
+    // /joinしたユーザーだけが質問・回答できる
+    pub participants_only: bool,
+    // /joinしたユーザーが順番に質問する
+    pub turn_based: bool,
+    // 生成する代わりに、ライブラリの評価の高い問題を出題する
+    pub reuse_library: bool,
+}
+
+// 許可チャンネルとチャンネルごとの設定。channels.json に保存する
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChannelConfig {
+    // 空の場合はすべてのチャンネルでゲームができる
+    pub allowed: HashSet<ChannelId>,
+    pub settings: HashMap<ChannelId, ChannelSettings>,
+}
@@ -1,5 +1,13 @@
+pub mod channel_settings;
 pub mod chat_completion;
-pub mod state;
+pub mod session;
+pub mod solo_stats;
+pub mod team;
+pub mod verdict;
 
+pub use channel_settings::{ChannelConfig, ChannelSettings, Difficulty};
 pub use chat_completion::{ChatCompletionMessage, Role};
-pub use state::State;
+pub use session::{DisplayMode, Session, SoloKind};
+pub use solo_stats::SoloStats;
+pub use team::Team;
+pub use verdict::Verdict;
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serenity::all::{ChannelId, MessageId, User, UserId};
+
+use crate::game::{Game, Player, PlayerId};
+use crate::i18n::Locale;
+use crate::models::{ChannelSettings, Team};
+
+// 質問への返答の表示方法
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DisplayMode {
+    // 質問ごとに返答をメッセージで送る
+    #[default]
+    Verbose,
+    // 返答はQ&Aボードにまとめて表示する
+    Compact,
+}
+
+// ユーザーごとのセッションで一人で遊ぶゲームの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoloKind {
+    // /solo
+    Practice,
+    // /daily とその問題の日付
+    Daily(NaiveDate),
+}
+
+// チャンネル（またはスレッド）ごとのゲームの状態
+pub struct Session {
+    pub join_users: Vec<UserId>,
+    pub scores: HashMap<UserId, u32>,
+    // 出題中の問題の進行
+    pub game: Game,
+    // スレッドで遊んでいる場合は親チャンネル
+    pub parent_channel: Option<ChannelId>,
+    // 通常のメッセージでの質問・回答を受け付けるかどうか
+    pub chat_mode: bool,
+    pub display: DisplayMode,
+    // コンパクト表示で更新し続けるQ&Aボードのメッセージ
+    pub board: Option<MessageId>,
+    // /playを実行した時点のチャンネルの設定
+    pub settings: ChannelSettings,
+    // 出題するたびに増える。制限時間のタイマーが古い問題に反応しないようにする
+    pub round: u32,
+    // 制限時間が切れる時刻（UNIX時間）。タイマーが古い制限時間に反応しないようにする
+    pub deadline: Option<i64>,
+    // 一時停止した時点の制限時間の残り（秒）。再開したときにこの時間でタイマーを開始する
+    pub remaining: Option<i64>,
+    // セッションを開始（リセット）した時刻。セッションのランキングの集計に使う
+    pub started_at: i64,
+    // 手番制の場合、join_users の何番目のユーザーの番か
+    pub turn: usize,
+    // 手番が変わるたびに増える。手番の制限時間のタイマーが古い手番に反応しないようにする
+    pub turn_serial: u32,
+    // このチャンネルで作成されたチーム
+    pub teams: Vec<Team>,
+    // チーム戦のスレッドの場合はチーム名
+    pub team: Option<String>,
+    // DMで遊んでいる場合は相手のユーザー。成績はランキングではなく個人の成績に記録する
+    pub dm_user: Option<UserId>,
+    // /solo・/daily のセッションの場合はその種類。結果は本人にだけ表示する
+    pub solo: Option<SoloKind>,
+}
+
+impl Session {
+    pub fn new(settings: ChannelSettings) -> Self {
+        Self {
+            join_users: vec![],
+            scores: HashMap::new(),
+            game: Game::new(settings.locale.unwrap_or_default()),
+            parent_channel: None,
+            chat_mode: false,
+            display: settings.display,
+            board: None,
+            settings,
+            round: 0,
+            deadline: None,
+            remaining: None,
+            started_at: Utc::now().timestamp(),
+            turn: 0,
+            turn_serial: 0,
+            teams: vec![],
+            team: None,
+            dm_user: None,
+            solo: None,
+        }
+    }
+
+    // スレッド用のセッションを作成する
+    pub fn in_thread(locale: Locale, parent_channel: ChannelId, settings: ChannelSettings) -> Self {
+        Self {
+            game: Game::new(locale),
+            parent_channel: Some(parent_channel),
+            ..Self::new(settings)
+        }
+    }
+
+    // チーム戦のスレッド用のセッションを作成する
+    // 親チャンネルで出題した問題を引き継ぎ、チームのメンバーだけが質問・回答できるようにする
+    pub fn for_team(parent: &Session, parent_channel: ChannelId, team: &Team) -> Self {
+        let mut settings = parent.settings.clone();
+        settings.participants_only = true;
+
+        Self {
+            join_users: team.members.clone(),
+            game: parent.game.fork(),
+            round: parent.round,
+            team: Some(team.name.clone()),
+            ..Self::in_thread(parent.game.locale, parent_channel, settings)
+        }
+    }
+
+    // 手番制の場合、質問できるユーザー。参加者がいない場合は誰でも質問できる
+    pub fn current_turn(&self) -> Option<UserId> {
+        if !self.settings.turn_based || self.join_users.is_empty() {
+            return None;
+        }
+        Some(self.join_users[self.turn % self.join_users.len()])
+    }
+
+    pub fn advance_turn(&mut self) {
+        if !self.join_users.is_empty() {
+            self.turn = (self.turn + 1) % self.join_users.len();
+        }
+        self.turn_serial += 1;
+    }
+
+    // チーム戦の親チャンネルで、チームのスレッドが同じ問題に挑んでいる最中かどうか
+    // 親チャンネルで回答・ギブアップすると、スレッドの勝敗が決まる前に問題が終わってしまう
+    pub fn has_team_threads(&self) -> bool {
+        self.teams.iter().any(|team| team.thread.is_some())
+    }
+
+    // /joinしたユーザーだけが遊べる設定の場合、参加していないユーザーは質問・回答できない
+    pub fn can_play(&self, user_id: UserId) -> bool {
+        !self.settings.participants_only || self.join_users.contains(&user_id)
+    }
+
+    // ゲームの進行状況とスコアを破棄する。スレッドとの紐付けと設定は残す
+    pub fn reset(&mut self) {
+        *self = Self {
+            game: Game::new(self.game.locale),
+            parent_channel: self.parent_channel,
+            dm_user: self.dm_user,
+            solo: self.solo,
+            ..Self::new(self.settings.clone())
+        };
+    }
+
+    // 会話履歴を指定したロケールのシステムプロンプトだけにする
+    // 前の問題の質問の記録とQ&Aボードも破棄する
+    pub fn reset_messages(&mut self, locale: Locale, system_prompt: String) {
+        self.game.reset(locale, system_prompt);
+        // 前のチーム戦のスレッドとの紐付けを外す。チーム戦ならこの後で作り直す
+        for team in &mut self.teams {
+            team.thread = None;
+        }
+        self.turn_serial += 1;
+        self.board = None;
+        self.round += 1;
+    }
+}
+
+// Discordのユーザーをゲームのプレイヤーとして扱う
+impl From<&User> for Player {
+    fn from(user: &User) -> Self {
+        Player {
+            id: user.id.into(),
+            name: user.display_name().to_string(),
+        }
+    }
+}
+
+impl From<UserId> for PlayerId {
+    fn from(user_id: UserId) -> Self {
+        PlayerId(user_id.get())
+    }
+}
+
+impl From<PlayerId> for UserId {
+    fn from(player_id: PlayerId) -> Self {
+        UserId::new(player_id.0)
+    }
+}
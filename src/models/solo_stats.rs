@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+// ソロモードの個人成績。solo_stats.json にユーザーごとに保存する
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct SoloStats {
+    pub played: u32,
+    pub solved: u32,
+    pub gave_up: u32,
+    // 正解した問題で使った質問とヒントの合計
+    pub questions: u32,
+    pub hints: u32,
+    // 最も少ない質問数で正解した記録
+    pub best: Option<u32>,
+}
+
+impl SoloStats {
+    pub fn record_solve(&mut self, questions: u32, hints: u32) {
+        self.solved += 1;
+        self.questions += questions;
+        self.hints += hints;
+        self.best = Some(self.best.map_or(questions, |best| best.min(questions)));
+    }
+
+    pub fn average_questions(&self) -> f64 {
+        if self.solved == 0 {
+            0.0
+        } else {
+            self.questions as f64 / self.solved as f64
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use serenity::all::{ChannelId, UserId};
+
+// チーム戦のチーム
+#[derive(Debug, Clone)]
+pub struct Team {
+    pub name: String,
+    pub members: Vec<UserId>,
+    // チーム戦で勝ったときに、正解したチームの得点の合計を加算する
+    pub score: u32,
+    // チーム戦の最中にこのチームが質問するスレッド
+    pub thread: Option<ChannelId>,
+}
+
+impl Team {
+    pub fn new(name: String, leader: UserId) -> Self {
+        Self {
+            name,
+            members: vec![leader],
+            score: 0,
+            thread: None,
+        }
+    }
+}
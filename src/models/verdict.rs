@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use crate::constants::prompt::GamePrompt;
+
+// 質問に対するゲームマスターの返答の種類
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Yes,
+    No,
+    Irrelevant,
+}
+
+impl Verdict {
+    // ゲームマスターの返答の書き出しから判定する
+    pub fn parse(reply: &str, prompt: &GamePrompt) -> Self {
+        let reply = reply.trim_start();
+
+        if prompt.yes.iter().any(|word| starts_with_word(reply, word)) {
+            Verdict::Yes
+        } else if prompt.no.iter().any(|word| starts_with_word(reply, word)) {
+            Verdict::No
+        } else {
+            Verdict::Irrelevant
+        }
+    }
+
+    pub fn emoji(self) -> char {
+        match self {
+            Verdict::Yes => '✅',
+            Verdict::No => '❌',
+            Verdict::Irrelevant => '➖',
+        }
+    }
+}
+
+// "No"が"Not"にマッチしないよう、英単語の途中で切れていないことも確認する
+fn starts_with_word(text: &str, word: &str) -> bool {
+    text.starts_with(word)
+        && !text[word.len()..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic())
+}
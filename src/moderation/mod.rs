@@ -0,0 +1,296 @@
+// ユーザーの質問・回答と、生成された問題・ストーリーのモデレーション
+// 判定方法（OpenAIのモデレーションAPI・NGワード・なし）と、該当した場合の対応を設定できる
+
+use std::fmt;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serenity::all::{ChannelId, UserId};
+use tokio::sync::Mutex;
+use tracing::error;
+
+use crate::api::OpenAIClient;
+use crate::i18n::{t, Locale};
+use crate::storage::Store;
+
+// 保存するログの上限
+const LOG_LIMIT: usize = 500;
+
+pub enum Moderator {
+    None,
+    // 小文字にした単語のいずれかを含む場合に該当とする
+    WordList(Vec<String>),
+    OpenAI,
+}
+
+impl Moderator {
+    // 設定の値が誤っている場合にモデレーションなしで動かないよう、起動を止める
+    pub fn from_config(kind: &str, words: &[String]) -> Self {
+        match kind {
+            "openai" => Moderator::OpenAI,
+            "wordlist" => Moderator::WordList(words.iter().map(|w| w.to_lowercase()).collect()),
+            "none" | "" => Moderator::None,
+            _ => panic!(
+                "MODERATION must be one of openai / wordlist / none, but was '{}'",
+                kind
+            ),
+        }
+    }
+
+    // 該当したカテゴリ（NGワードの場合は単語）を返す。該当しない場合は空
+    async fn check(&self, client: &OpenAIClient, text: &str) -> Result<Vec<String>, anyhow::Error> {
+        match self {
+            Moderator::None => Ok(vec![]),
+            Moderator::WordList(words) => Ok(matched_words(words, text)),
+            Moderator::OpenAI => client.moderate(text).await,
+        }
+    }
+}
+
+// 文章に含まれるNGワード。大文字・小文字は区別しない
+fn matched_words(words: &[String], text: &str) -> Vec<String> {
+    let text = text.to_lowercase();
+    words
+        .iter()
+        .filter(|word| text.contains(word.as_str()))
+        .cloned()
+        .collect()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationAction {
+    // 送信・表示しない
+    Block,
+    // 記録して警告するが、そのまま続ける
+    Warn,
+    // 問題を作り直す。ユーザーの入力やストーリーに対してはBlockと同じ
+    Regenerate,
+}
+
+impl ModerationAction {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "block" => Some(ModerationAction::Block),
+            "warn" => Some(ModerationAction::Warn),
+            "regenerate" => Some(ModerationAction::Regenerate),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Target {
+    Question,
+    Answer,
+    Puzzle,
+    Story,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModerationRecord {
+    // UNIX時間（秒）
+    pub timestamp: i64,
+    pub channel_id: ChannelId,
+    pub user_id: Option<UserId>,
+    pub target: Target,
+    pub text: String,
+    pub categories: Vec<String>,
+    pub action: ModerationAction,
+}
+
+// 生成した問題がモデレーションでブロックされたことを表すエラー
+#[derive(Debug)]
+pub struct Blocked;
+
+impl fmt::Display for Blocked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "モデレーションによりブロックされました")
+    }
+}
+
+impl std::error::Error for Blocked {}
+
+pub struct Moderation {
+    moderator: Moderator,
+    // ユーザーの質問・回答に対する対応
+    user_action: ModerationAction,
+    // 生成した問題・ストーリーに対する対応
+    puzzle_action: ModerationAction,
+    pub log: Mutex<Vec<ModerationRecord>>,
+    store: Store,
+}
+
+impl Moderation {
+    pub fn new(
+        moderator: Moderator,
+        user_action: ModerationAction,
+        puzzle_action: ModerationAction,
+        store: Store,
+    ) -> Self {
+        Self {
+            moderator,
+            user_action,
+            puzzle_action,
+            log: Mutex::new(store.load("moderation_log")),
+            store,
+        }
+    }
+
+    // 判定して、該当した場合は取るべき対応を返す
+    // モデレーション自体が失敗した場合はゲームを止めないよう該当なしとして扱う
+    pub async fn screen(
+        &self,
+        client: &OpenAIClient,
+        target: Target,
+        text: &str,
+        channel_id: ChannelId,
+        user_id: Option<UserId>,
+    ) -> Option<ModerationAction> {
+        let categories = match self.moderator.check(client, text).await {
+            Ok(categories) if categories.is_empty() => return None,
+            Ok(categories) => categories,
+            Err(e) => {
+                error!("モデレーションに失敗しました: {:?}", e);
+                return None;
+            }
+        };
+
+        let action = self.action(target);
+        self.record(ModerationRecord {
+            timestamp: Utc::now().timestamp(),
+            channel_id,
+            user_id,
+            target,
+            text: text.to_string(),
+            categories,
+            action,
+        })
+        .await;
+
+        Some(action)
+    }
+
+    // 該当した場合の対応。作り直せるのは生成した問題だけなので、それ以外はブロックする
+    fn action(&self, target: Target) -> ModerationAction {
+        match (target, self.user_action, self.puzzle_action) {
+            (Target::Question | Target::Answer, ModerationAction::Regenerate, _) => {
+                ModerationAction::Block
+            }
+            (Target::Question | Target::Answer, action, _) => action,
+            (Target::Story, _, ModerationAction::Regenerate) => ModerationAction::Block,
+            (Target::Puzzle | Target::Story, _, action) => action,
+        }
+    }
+
+    // ストーリーが該当してブロックする場合は伏せた文面に置き換える
+    pub async fn filter_story(
+        &self,
+        client: &OpenAIClient,
+        story: String,
+        channel_id: ChannelId,
+        locale: Locale,
+    ) -> String {
+        match self
+            .screen(client, Target::Story, &story, channel_id, None)
+            .await
+        {
+            Some(ModerationAction::Block | ModerationAction::Regenerate) => {
+                t(locale, "moderation-story-hidden")
+            }
+            _ => story,
+        }
+    }
+
+    async fn record(&self, record: ModerationRecord) {
+        let mut log = self.log.lock().await;
+        log.push(record);
+        if log.len() > LOG_LIMIT {
+            let overflow = log.len() - LOG_LIMIT;
+            log.drain(..overflow);
+        }
+
+        if let Err(e) = self.store.save("moderation_log", &*log) {
+            error!("モデレーションログの保存に失敗しました: {:?}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn moderation(user_action: ModerationAction, puzzle_action: ModerationAction) -> Moderation {
+        let dir = std::env::temp_dir().join(format!("moderation-{}", std::process::id()));
+        Moderation::new(Moderator::None, user_action, puzzle_action, Store::new(dir))
+    }
+
+    #[test]
+    fn word_list_ignores_case() {
+        let Moderator::WordList(words) =
+            Moderator::from_config("wordlist", &["Spoiler".to_string(), "NG".to_string()])
+        else {
+            panic!("wordlist is not a word list");
+        };
+        assert_eq!(
+            matched_words(&words, "This is a SPOILER!"),
+            vec!["spoiler".to_string()]
+        );
+        assert_eq!(
+            matched_words(&words, "ng word and spoiler"),
+            vec!["spoiler".to_string(), "ng".to_string()]
+        );
+        assert!(matched_words(&words, "普通の質問です").is_empty());
+    }
+
+    #[test]
+    fn known_moderators_are_parsed() {
+        assert!(matches!(
+            Moderator::from_config("openai", &[]),
+            Moderator::OpenAI
+        ));
+        assert!(matches!(
+            Moderator::from_config("none", &[]),
+            Moderator::None
+        ));
+        assert!(matches!(Moderator::from_config("", &[]), Moderator::None));
+    }
+
+    #[test]
+    #[should_panic(expected = "MODERATION must be one of")]
+    fn unknown_moderator_stops_startup() {
+        Moderator::from_config("open-ai", &[]);
+    }
+
+    #[test]
+    fn user_input_is_never_regenerated() {
+        let moderation = moderation(ModerationAction::Regenerate, ModerationAction::Regenerate);
+        assert_eq!(moderation.action(Target::Question), ModerationAction::Block);
+        assert_eq!(moderation.action(Target::Answer), ModerationAction::Block);
+        assert_eq!(moderation.action(Target::Story), ModerationAction::Block);
+        assert_eq!(
+            moderation.action(Target::Puzzle),
+            ModerationAction::Regenerate
+        );
+    }
+
+    #[test]
+    fn each_target_uses_its_own_action() {
+        let moderation = moderation(ModerationAction::Warn, ModerationAction::Block);
+        assert_eq!(moderation.action(Target::Question), ModerationAction::Warn);
+        assert_eq!(moderation.action(Target::Answer), ModerationAction::Warn);
+        assert_eq!(moderation.action(Target::Puzzle), ModerationAction::Block);
+        assert_eq!(moderation.action(Target::Story), ModerationAction::Block);
+
+        let warn_only_puzzles = self::moderation(ModerationAction::Block, ModerationAction::Warn);
+        assert_eq!(
+            warn_only_puzzles.action(Target::Question),
+            ModerationAction::Block
+        );
+        assert_eq!(
+            warn_only_puzzles.action(Target::Story),
+            ModerationAction::Warn
+        );
+    }
+}
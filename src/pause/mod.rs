@@ -0,0 +1,163 @@
+// 一時停止したゲームの保存
+// /pause したセッションを保存し、ボットを再起動しても /resume で続きから遊べるようにする
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serenity::all::{ChannelId, UserId};
+use tokio::sync::Mutex;
+use tracing::error;
+
+use crate::game::Game;
+use crate::models::{ChannelSettings, DisplayMode, Session};
+use crate::storage::Store;
+
+// 一時停止した時点のセッション
+// チームとQ&Aボードは保存しない（チーム戦では一時停止できない）
+// 一時停止した時刻はゲームに含まれる
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PausedSession {
+    pub game: Game,
+    #[serde(default)]
+    pub join_users: Vec<UserId>,
+    #[serde(default)]
+    pub scores: HashMap<UserId, u32>,
+    #[serde(default)]
+    pub parent_channel: Option<ChannelId>,
+    #[serde(default)]
+    pub chat_mode: bool,
+    #[serde(default)]
+    pub display: DisplayMode,
+    #[serde(default)]
+    pub settings: ChannelSettings,
+    pub started_at: i64,
+    #[serde(default)]
+    pub turn: usize,
+    #[serde(default)]
+    pub dm_user: Option<UserId>,
+    // 制限時間の残り（秒）
+    #[serde(default)]
+    pub remaining: Option<i64>,
+}
+
+impl PausedSession {
+    fn from_session(session: &Session) -> Self {
+        Self {
+            game: session.game.clone(),
+            join_users: session.join_users.clone(),
+            scores: session.scores.clone(),
+            parent_channel: session.parent_channel,
+            chat_mode: session.chat_mode,
+            display: session.display,
+            settings: session.settings.clone(),
+            started_at: session.started_at,
+            turn: session.turn,
+            dm_user: session.dm_user,
+            remaining: session.remaining,
+        }
+    }
+
+    fn into_session(self) -> Session {
+        Session {
+            game: self.game,
+            join_users: self.join_users,
+            scores: self.scores,
+            parent_channel: self.parent_channel,
+            chat_mode: self.chat_mode,
+            display: self.display,
+            started_at: self.started_at,
+            turn: self.turn,
+            dm_user: self.dm_user,
+            remaining: self.remaining,
+            ..Session::new(self.settings)
+        }
+    }
+}
+
+pub struct PausedGames {
+    games: Mutex<HashMap<ChannelId, PausedSession>>,
+    store: Store,
+}
+
+impl PausedGames {
+    pub fn new(store: Store) -> Self {
+        Self {
+            games: Mutex::new(store.load("paused_games")),
+            store,
+        }
+    }
+
+    // 保存されている一時停止中のゲームのセッション。起動時にチャンネルのセッションとして復元する
+    pub fn sessions(&mut self) -> HashMap<ChannelId, Arc<Mutex<Session>>> {
+        self.games
+            .get_mut()
+            .iter()
+            .map(|(channel_id, paused)| {
+                let session = paused.clone().into_session();
+                (*channel_id, Arc::new(Mutex::new(session)))
+            })
+            .collect()
+    }
+
+    pub async fn save(&self, channel_id: ChannelId, session: &Session) {
+        let mut games = self.games.lock().await;
+        games.insert(channel_id, PausedSession::from_session(session));
+        self.persist(&games);
+    }
+
+    // 再開・終了したゲームを削除する
+    pub async fn remove(&self, channel_id: ChannelId) {
+        let mut games = self.games.lock().await;
+        if games.remove(&channel_id).is_some() {
+            self.persist(&games);
+        }
+    }
+
+    fn persist(&self, games: &HashMap<ChannelId, PausedSession>) {
+        if let Err(e) = self.store.save("paused_games", games) {
+            error!("一時停止したゲームの保存に失敗しました: {:?}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::State;
+
+    #[tokio::test]
+    async fn paused_session_is_restored_after_restart() {
+        let dir = std::env::temp_dir().join(format!("paused-games-{}", std::process::id()));
+        let store = Store::new(&dir);
+        let channel_id = ChannelId::new(42);
+        let user_id = UserId::new(7);
+
+        let mut session = Session::new(ChannelSettings::default());
+        session.join_users.push(user_id);
+        session.scores.insert(user_id, 3);
+        session.game.generate().unwrap();
+        session.game.problem = "問題".to_string();
+        session.game.start().unwrap();
+        session.game.pause().unwrap();
+        session.remaining = Some(90);
+        PausedGames::new(store.clone())
+            .save(channel_id, &session)
+            .await;
+
+        // 再起動したときと同じく、保存したファイルから読み込む
+        let mut sessions = PausedGames::new(store.clone()).sessions();
+        let restored = sessions.remove(&channel_id).unwrap();
+        let restored = restored.lock().await;
+        assert_eq!(restored.game.state(), State::Paused);
+        assert_eq!(restored.game.problem, "問題");
+        assert_eq!(restored.join_users, vec![user_id]);
+        assert_eq!(restored.scores[&user_id], 3);
+        assert_eq!(restored.remaining, Some(90));
+
+        // 再開したゲームは次の起動では復元しない
+        PausedGames::new(store.clone()).remove(channel_id).await;
+        assert!(PausedGames::new(store).sessions().is_empty());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
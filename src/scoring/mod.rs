@@ -0,0 +1,376 @@
+// 正解したときの得点の計算
+// 少ない質問数・短い時間で解けたほど高得点になり、ヒントや誤答で減点される
+// 「はい」となった質問をした参加者にもアシストの得点が入る
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serenity::all::UserId;
+
+use crate::models::{Session, Verdict};
+
+// 得点の計算ルール。データディレクトリの scoring.json で変更できる
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ScoringRules {
+    // 正解した人の基本点
+    pub base: u32,
+    // 質問数がこれを超えると1問ごとに `question_penalty` 減点する
+    pub par_questions: usize,
+    pub question_penalty: u32,
+    // 経過時間（分）がこれを超えると1分ごとに `minute_penalty` 減点する
+    pub par_minutes: u64,
+    pub minute_penalty: u32,
+    // ヒント1回ごとの減点
+    pub hint_penalty: u32,
+    // 減点しても正解した人に必ず入る点
+    pub min_points: u32,
+    // 「はい」となった質問1つごとのアシスト点とその上限
+    pub assist_points: u32,
+    pub max_assist: u32,
+    // 誤答1回ごとの減点（その問題で得た点から引く）
+    pub wrong_answer_penalty: u32,
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        Self {
+            base: 100,
+            par_questions: 10,
+            question_penalty: 3,
+            par_minutes: 5,
+            minute_penalty: 2,
+            hint_penalty: 15,
+            min_points: 20,
+            assist_points: 5,
+            max_assist: 30,
+            wrong_answer_penalty: 5,
+        }
+    }
+}
+
+// 得点の内訳の1項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreItem {
+    Solved(u32),
+    // 減点・質問数
+    Questions(u32, usize),
+    // 減点・経過時間（分）
+    Time(u32, u64),
+    // 減点・ヒントの回数
+    Hints(u32, usize),
+    // 加点・「はい」となった質問の数
+    Assist(u32, usize),
+    // 減点・誤答の回数
+    WrongAnswers(u32, u32),
+}
+
+// 1人分の得点と内訳
+pub struct Award {
+    pub user_id: UserId,
+    pub points: u32,
+    pub items: Vec<ScoreItem>,
+}
+
+impl ScoringRules {
+    // 正解した時点のセッションの状態から、参加者ごとの得点を計算する
+    // 正解した人を先頭に、得点の高い順に並べる
+    pub fn score(&self, solver: UserId, session: &Session, now: i64) -> Vec<Award> {
+        let questions = session.game.qa_log.len();
+        let minutes = (now - session.game.puzzle_started_at).max(0) as u64 / 60;
+        let hints = session.game.hints.len();
+
+        // scoring.json の値が大きくても桁あふれしないよう、すべて飽和演算で計算する
+        let question_penalty = count(questions.saturating_sub(self.par_questions))
+            .saturating_mul(self.question_penalty);
+        let time_penalty =
+            count(minutes.saturating_sub(self.par_minutes)).saturating_mul(self.minute_penalty);
+        let hint_penalty = count(hints).saturating_mul(self.hint_penalty);
+        let solved = self
+            .base
+            .saturating_sub(
+                question_penalty
+                    .saturating_add(time_penalty)
+                    .saturating_add(hint_penalty),
+            )
+            .max(self.min_points);
+
+        let mut yes_counts: HashMap<UserId, usize> = HashMap::new();
+        for entry in &session.game.qa_log {
+            if entry.verdict == Verdict::Yes {
+                *yes_counts.entry(entry.asker.into()).or_default() += 1;
+            }
+        }
+
+        let mut users: Vec<UserId> = vec![solver];
+        let wrong_users = session
+            .game
+            .wrong_answers
+            .keys()
+            .map(|&player| player.into());
+        for user in yes_counts.keys().copied().chain(wrong_users) {
+            if !users.contains(&user) {
+                users.push(user);
+            }
+        }
+
+        let mut awards: Vec<Award> = users
+            .into_iter()
+            .map(|user_id| {
+                let mut items = vec![];
+                let mut points: u32 = 0;
+
+                if user_id == solver {
+                    items.push(ScoreItem::Solved(self.base));
+                    if question_penalty > 0 {
+                        items.push(ScoreItem::Questions(question_penalty, questions));
+                    }
+                    if time_penalty > 0 {
+                        items.push(ScoreItem::Time(time_penalty, minutes));
+                    }
+                    if hint_penalty > 0 {
+                        items.push(ScoreItem::Hints(hint_penalty, hints));
+                    }
+                    points = points.saturating_add(solved);
+                }
+
+                let yes_count = yes_counts.get(&user_id).copied().unwrap_or_default();
+                if yes_count > 0 {
+                    let assist = count(yes_count)
+                        .saturating_mul(self.assist_points)
+                        .min(self.max_assist);
+                    items.push(ScoreItem::Assist(assist, yes_count));
+                    points = points.saturating_add(assist);
+                }
+
+                let wrong = session
+                    .game
+                    .wrong_answers
+                    .get(&user_id.into())
+                    .copied()
+                    .unwrap_or_default();
+                if wrong > 0 {
+                    let penalty = wrong.saturating_mul(self.wrong_answer_penalty);
+                    items.push(ScoreItem::WrongAnswers(penalty, wrong));
+                    points = points.saturating_sub(penalty);
+                }
+
+                Award {
+                    user_id,
+                    points,
+                    items,
+                }
+            })
+            .collect();
+
+        awards[1..].sort_by_key(|award| Reverse(award.points));
+        awards
+    }
+}
+
+// 回数・分数を得点の計算に使う u32 にする。収まらない場合は u32::MAX とみなす
+fn count<T: TryInto<u32>>(n: T) -> u32 {
+    n.try_into().unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{PlayerId, QaEntry};
+    use crate::models::ChannelSettings;
+
+    const SOLVER: UserId = UserId::new(1);
+    const HELPER: UserId = UserId::new(2);
+    const STARTED_AT: i64 = 1_000_000;
+
+    fn session(questions: usize, hints: usize) -> Session {
+        let mut session = Session::new(ChannelSettings::default());
+        session.game.puzzle_started_at = STARTED_AT;
+        for i in 0..questions {
+            session.game.qa_log.push(QaEntry {
+                asker: PlayerId(SOLVER.get()),
+                asker_name: String::new(),
+                question: format!("質問{}", i),
+                verdict: Verdict::No,
+                reply: String::new(),
+                asked_at: STARTED_AT,
+            });
+        }
+        session.game.hints = vec!["ヒント".to_string(); hints];
+        session
+    }
+
+    fn solver_points(rules: &ScoringRules, session: &Session, minutes: i64) -> u32 {
+        let awards = rules.score(SOLVER, session, STARTED_AT + minutes * 60);
+        assert_eq!(awards[0].user_id, SOLVER);
+        awards[0].points
+    }
+
+    #[test]
+    fn within_par_gets_full_points() {
+        let rules = ScoringRules::default();
+        let session = session(rules.par_questions, 0);
+        let awards = rules.score(SOLVER, &session, STARTED_AT + rules.par_minutes as i64 * 60);
+        assert_eq!(awards[0].points, rules.base);
+        assert_eq!(awards[0].items, vec![ScoreItem::Solved(rules.base)]);
+    }
+
+    #[test]
+    fn questions_and_minutes_beyond_par_are_deducted() {
+        let rules = ScoringRules::default();
+        let session = session(rules.par_questions + 4, 0);
+        let awards = rules.score(
+            SOLVER,
+            &session,
+            STARTED_AT + (rules.par_minutes as i64 + 3) * 60,
+        );
+        let question_penalty = 4 * rules.question_penalty;
+        let time_penalty = 3 * rules.minute_penalty;
+        assert_eq!(
+            awards[0].items,
+            vec![
+                ScoreItem::Solved(rules.base),
+                ScoreItem::Questions(question_penalty, rules.par_questions + 4),
+                ScoreItem::Time(time_penalty, rules.par_minutes + 3),
+            ]
+        );
+        assert_eq!(
+            awards[0].points,
+            rules.base - question_penalty - time_penalty
+        );
+    }
+
+    #[test]
+    fn paused_time_is_not_deducted() {
+        let rules = ScoringRules::default();
+        let mut session = session(0, 0);
+        session.game.generate().unwrap();
+        session.game.start().unwrap();
+        session.game.puzzle_started_at = STARTED_AT;
+
+        // 2分遊んで一晩止め、再開してから3分で正解した
+        let paused_at = STARTED_AT + 2 * 60;
+        let resumed_at = paused_at + 10 * 60 * 60;
+        session.game.pause_at(paused_at).unwrap();
+        session.game.resume_at(resumed_at).unwrap();
+        session.game.commit();
+        let awards = rules.score(SOLVER, &session, resumed_at + 3 * 60);
+        assert_eq!(awards[0].items, vec![ScoreItem::Solved(rules.base)]);
+
+        // 一時停止の前後を合わせて基準を超えた分だけ減点する
+        let awards = rules.score(SOLVER, &session, resumed_at + 5 * 60);
+        assert_eq!(
+            awards[0].items,
+            vec![
+                ScoreItem::Solved(rules.base),
+                ScoreItem::Time(2 * rules.minute_penalty, 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn each_hint_is_deducted() {
+        let rules = ScoringRules::default();
+        let session = session(0, 2);
+        let awards = rules.score(SOLVER, &session, STARTED_AT);
+        assert_eq!(
+            awards[0].items,
+            vec![
+                ScoreItem::Solved(rules.base),
+                ScoreItem::Hints(2 * rules.hint_penalty, 2),
+            ]
+        );
+        assert_eq!(awards[0].points, rules.base - 2 * rules.hint_penalty);
+    }
+
+    #[test]
+    fn deductions_stop_at_min_points() {
+        let rules = ScoringRules::default();
+        let session = session(rules.par_questions + 100, 5);
+        assert_eq!(solver_points(&rules, &session, 600), rules.min_points);
+
+        // 最低点が0でも引きすぎて繰り下がらない
+        let rules = ScoringRules {
+            min_points: 0,
+            ..ScoringRules::default()
+        };
+        assert_eq!(solver_points(&rules, &session, 600), 0);
+    }
+
+    fn yes_entry(asker: UserId) -> QaEntry {
+        QaEntry {
+            asker: PlayerId(asker.get()),
+            asker_name: String::new(),
+            question: "質問".to_string(),
+            verdict: Verdict::Yes,
+            reply: String::new(),
+            asked_at: STARTED_AT,
+        }
+    }
+
+    #[test]
+    fn assist_stops_at_max_assist() {
+        let rules = ScoringRules::default();
+        let mut session = session(0, 0);
+        let yes_count = (rules.max_assist / rules.assist_points) as usize + 3;
+        for _ in 0..yes_count {
+            session.game.qa_log.push(yes_entry(HELPER));
+        }
+
+        let awards = rules.score(SOLVER, &session, STARTED_AT);
+        let helper = awards.iter().find(|award| award.user_id == HELPER).unwrap();
+        assert_eq!(helper.points, rules.max_assist);
+        assert_eq!(
+            helper.items,
+            vec![ScoreItem::Assist(rules.max_assist, yes_count)]
+        );
+    }
+
+    #[test]
+    fn huge_rules_do_not_overflow() {
+        let rules = ScoringRules {
+            base: u32::MAX,
+            question_penalty: u32::MAX,
+            minute_penalty: u32::MAX,
+            hint_penalty: u32::MAX,
+            assist_points: u32::MAX,
+            max_assist: u32::MAX,
+            wrong_answer_penalty: u32::MAX,
+            ..ScoringRules::default()
+        };
+        let mut session = session(rules.par_questions + 2, 2);
+        session.game.qa_log.push(yes_entry(SOLVER));
+        session.game.qa_log.push(yes_entry(HELPER));
+        session.game.qa_log.push(yes_entry(HELPER));
+        session.game.wrong_answers.insert(PlayerId(HELPER.get()), 3);
+
+        let awards = rules.score(SOLVER, &session, STARTED_AT + 600 * 60);
+        // 減点が基本点を上回るので最低点にアシストの上限が足される
+        assert_eq!(awards[0].points, rules.min_points.saturating_add(u32::MAX));
+        let helper = awards.iter().find(|award| award.user_id == HELPER).unwrap();
+        assert_eq!(helper.points, 0);
+    }
+
+    #[test]
+    fn wrong_answers_never_go_below_zero() {
+        let rules = ScoringRules::default();
+        let mut session = session(0, 0);
+        session.game.qa_log.push(yes_entry(HELPER));
+        session
+            .game
+            .wrong_answers
+            .insert(PlayerId(HELPER.get()), 10);
+
+        let awards = rules.score(SOLVER, &session, STARTED_AT);
+        let helper = awards.iter().find(|award| award.user_id == HELPER).unwrap();
+        assert_eq!(helper.points, 0);
+        assert_eq!(
+            helper.items,
+            vec![
+                ScoreItem::Assist(rules.assist_points, 1),
+                ScoreItem::WrongAnswers(10 * rules.wrong_answer_penalty, 10),
+            ]
+        );
+    }
+}
@@ -0,0 +1,86 @@
+// JSONファイルへの永続化
+// 設定やランキングなど、Botを再起動しても残したいデータを保存する
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::{error, warn};
+
+#[derive(Clone)]
+pub struct Store {
+    dir: PathBuf,
+}
+
+impl Store {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    // 保存されていない場合や読み込めない場合はデフォルト値を返す
+    // 壊れたファイルは次の保存で上書きされないよう `<name>.json.corrupt` に退避する
+    pub fn load<T: DeserializeOwned + Default>(&self, name: &str) -> T {
+        let path = self.path(name);
+        let Ok(json) = fs::read_to_string(&path) else {
+            return T::default();
+        };
+
+        serde_json::from_str(&json).unwrap_or_else(|e| {
+            let corrupt = path.with_extension("json.corrupt");
+            warn!(
+                "{} の読み込みに失敗したため {} に退避します: {}",
+                path.display(),
+                corrupt.display(),
+                e
+            );
+            if let Err(e) = fs::rename(&path, &corrupt) {
+                error!("{} の退避に失敗しました: {}", path.display(), e);
+            }
+            T::default()
+        })
+    }
+
+    // 書き込み途中で落ちてもファイルが壊れないよう、一時ファイルに書いてから置き換える
+    pub fn save<T: Serialize>(&self, name: &str, value: &T) -> Result<(), anyhow::Error> {
+        fs::create_dir_all(&self.dir)?;
+
+        let path = self.path(name);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(value)?)?;
+        fs::rename(tmp, path)?;
+
+        Ok(())
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corrupt_file_is_moved_aside() {
+        let dir = std::env::temp_dir().join(format!("store-{}", std::process::id()));
+        let store = Store::new(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("scores.json"), "{ broken").unwrap();
+
+        let scores: Vec<u32> = store.load("scores");
+        assert!(scores.is_empty());
+        assert_eq!(
+            fs::read_to_string(dir.join("scores.json.corrupt")).unwrap(),
+            "{ broken"
+        );
+
+        // 次の保存で壊れたファイルの中身が失われない
+        store.save("scores", &vec![1u32]).unwrap();
+        let scores: Vec<u32> = store.load("scores");
+        assert_eq!(scores, vec![1]);
+        assert!(dir.join("scores.json.corrupt").exists());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,138 @@
+// プレイヤーが投稿した問題と、その審査の状態
+// 投稿された問題はモデレーターが承認するまで出題せず、承認した問題は作者の名前付きでライブラリに追加する
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serenity::all::UserId;
+use tokio::sync::Mutex;
+use tracing::error;
+
+use crate::i18n::Locale;
+use crate::models::Difficulty;
+use crate::storage::Store;
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SubmissionStatus {
+    #[default]
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Submission {
+    pub id: u64,
+    pub author_id: UserId,
+    pub author_name: String,
+    pub title: String,
+    pub problem: String,
+    pub solution: String,
+    // 出題中に /hint で順に出すヒント
+    #[serde(default)]
+    pub hints: Vec<String>,
+    pub difficulty: Difficulty,
+    pub locale: Locale,
+    #[serde(default)]
+    pub status: SubmissionStatus,
+    pub submitted_at: i64,
+    // 承認した場合はライブラリでの問題のID
+    #[serde(default)]
+    pub puzzle_id: Option<u64>,
+}
+
+// 投稿フォームの入力内容
+pub struct Draft {
+    pub title: String,
+    pub problem: String,
+    pub solution: String,
+    pub hints: Vec<String>,
+    pub difficulty: Difficulty,
+    pub locale: Locale,
+}
+
+pub struct Submissions {
+    submissions: Mutex<Vec<Submission>>,
+    store: Store,
+}
+
+impl Submissions {
+    pub fn new(store: Store) -> Self {
+        Self {
+            submissions: Mutex::new(store.load("submissions")),
+            store,
+        }
+    }
+
+    // 審査待ちとして追加する
+    pub async fn add(&self, author_id: UserId, author_name: String, draft: Draft) -> Submission {
+        let mut submissions = self.submissions.lock().await;
+        let submission = Submission {
+            id: submissions
+                .iter()
+                .map(|submission| submission.id)
+                .max()
+                .unwrap_or(0)
+                + 1,
+            author_id,
+            author_name,
+            title: draft.title,
+            problem: draft.problem,
+            solution: draft.solution,
+            hints: draft.hints,
+            difficulty: draft.difficulty,
+            locale: draft.locale,
+            status: SubmissionStatus::Pending,
+            submitted_at: Utc::now().timestamp(),
+            puzzle_id: None,
+        };
+        submissions.push(submission.clone());
+
+        self.save(&submissions);
+        submission
+    }
+
+    // 一番古い審査待ちの投稿と、審査待ちの件数
+    pub async fn oldest_pending(&self) -> (Option<Submission>, usize) {
+        let submissions = self.submissions.lock().await;
+        let pending: Vec<_> = submissions
+            .iter()
+            .filter(|submission| submission.status == SubmissionStatus::Pending)
+            .collect();
+        (
+            pending.first().map(|&submission| submission.clone()),
+            pending.len(),
+        )
+    }
+
+    // 審査待ちの投稿を承認・却下する。見つからない場合や審査済みの場合はNone
+    pub async fn review(&self, id: u64, status: SubmissionStatus) -> Option<Submission> {
+        let mut submissions = self.submissions.lock().await;
+        let submission = submissions.iter_mut().find(|submission| {
+            submission.id == id && submission.status == SubmissionStatus::Pending
+        })?;
+        submission.status = status;
+        let submission = submission.clone();
+
+        self.save(&submissions);
+        Some(submission)
+    }
+
+    // 承認した投稿にライブラリでの問題のIDを記録する
+    pub async fn set_puzzle_id(&self, id: u64, puzzle_id: u64) {
+        let mut submissions = self.submissions.lock().await;
+        if let Some(submission) = submissions
+            .iter_mut()
+            .find(|submission| submission.id == id)
+        {
+            submission.puzzle_id = Some(puzzle_id);
+            self.save(&submissions);
+        }
+    }
+
+    fn save(&self, submissions: &[Submission]) {
+        if let Err(e) = self.store.save("submissions", &submissions) {
+            error!("投稿された問題の保存に失敗しました: {:?}", e);
+        }
+    }
+}
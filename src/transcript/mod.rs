@@ -0,0 +1,317 @@
+// 終わった問題のやりとりの記録（トランスクリプト）
+// /export でJSON（機械向け）とMarkdown（共有向け）に書き出し、書き出したJSONは読み込んで分析や再出題に使える
+
+use chrono::{DateTime, FixedOffset, Utc};
+use fluent::fluent_args;
+use serde::{Deserialize, Serialize};
+use serenity::all::ChannelId;
+use tokio::sync::Mutex;
+use tracing::error;
+
+use crate::game::QaEntry;
+use crate::i18n::{t, t_args, Locale};
+use crate::models::{ChatCompletionMessage, Difficulty, Role, Session, Verdict};
+use crate::storage::Store;
+
+// 保存しておくトランスクリプトの数
+const TRANSCRIPT_LIMIT: usize = 100;
+
+// 問題の終わり方
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Ending {
+    Solved { solver: String },
+    GaveUp,
+    TimeUp,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Transcript {
+    pub id: u64,
+    pub channel_id: ChannelId,
+    // スレッドで遊んだ場合は親チャンネル
+    #[serde(default)]
+    pub parent_channel: Option<ChannelId>,
+    pub locale: Locale,
+    pub difficulty: Difficulty,
+    // ライブラリでの問題のID
+    #[serde(default)]
+    pub puzzle_id: Option<u64>,
+    pub problem: String,
+    // 出題中の問題を書き出す場合は含めない
+    #[serde(default)]
+    pub solution: Option<String>,
+    pub started_at: i64,
+    #[serde(default)]
+    pub finished_at: Option<i64>,
+    // 出題中の場合はNone
+    #[serde(default)]
+    pub ending: Option<Ending>,
+    #[serde(default)]
+    pub questions: Vec<QaEntry>,
+    #[serde(default)]
+    pub hints: Vec<String>,
+    // ゲームマスターとの会話。システムプロンプトは設定なので含めない
+    #[serde(default)]
+    pub messages: Vec<ChatCompletionMessage>,
+}
+
+// 読み込んだトランスクリプトの集計
+pub struct Summary {
+    pub yes: usize,
+    pub no: usize,
+    pub irrelevant: usize,
+    // 質問した人と質問の数。多い順
+    pub askers: Vec<(String, usize)>,
+    // 出題から終了（出題中の場合は最後の質問）までの秒数
+    pub duration: i64,
+}
+
+impl Transcript {
+    // セッションの問題の記録を作る。終わっていない問題は解答と会話を含めない
+    pub fn from_session(
+        id: u64,
+        channel_id: ChannelId,
+        session: &Session,
+        ending: Option<Ending>,
+    ) -> Self {
+        let finished = ending.is_some();
+        Self {
+            id,
+            channel_id,
+            parent_channel: session.parent_channel,
+            locale: session.game.locale,
+            difficulty: session.settings.difficulty,
+            puzzle_id: session.game.puzzle_id,
+            problem: session.game.problem.clone(),
+            solution: finished.then(|| session.game.solution.clone()),
+            started_at: session.game.puzzle_started_at,
+            finished_at: finished.then(|| Utc::now().timestamp()),
+            ending,
+            questions: session.game.qa_log.clone(),
+            hints: session.game.hints.clone(),
+            messages: if finished {
+                session
+                    .game
+                    .messages
+                    .iter()
+                    .filter(|message| !matches!(message.role, Role::System))
+                    .cloned()
+                    .collect()
+            } else {
+                vec![]
+            },
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, anyhow::Error> {
+        let transcript: Self = serde_json::from_str(json)?;
+        if transcript.problem.trim().is_empty() {
+            anyhow::bail!("problem is empty");
+        }
+        Ok(transcript)
+    }
+
+    pub fn to_json(&self) -> Result<String, anyhow::Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn to_markdown(&self, locale: Locale, offset: FixedOffset) -> String {
+        let time = |timestamp: i64| {
+            DateTime::from_timestamp(timestamp, 0)
+                .unwrap_or_default()
+                .with_timezone(&offset)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        };
+        let title = if self.id == 0 {
+            t(locale, "transcript-title-current")
+        } else {
+            t_args(locale, "transcript-title", &fluent_args!["id" => self.id])
+        };
+
+        let mut lines = vec![
+            format!("# {}", title),
+            String::new(),
+            format!(
+                "- {}: {}",
+                t(locale, "transcript-started"),
+                time(self.started_at)
+            ),
+            format!(
+                "- {}: {}",
+                t(locale, "admin-config-difficulty"),
+                t(
+                    locale,
+                    &format!(
+                        "choice-admin-config-difficulty-{}",
+                        self.difficulty.as_str()
+                    )
+                )
+            ),
+            format!(
+                "- {}: {}",
+                t(locale, "transcript-ending"),
+                self.ending_label(locale)
+            ),
+            String::new(),
+            format!("## {}", t(locale, "transcript-problem")),
+            String::new(),
+            self.problem.clone(),
+            String::new(),
+            format!("## {}", t(locale, "transcript-questions")),
+            String::new(),
+        ];
+        if self.questions.is_empty() {
+            lines.push(t(locale, "transcript-no-questions"));
+        }
+        for (index, entry) in self.questions.iter().enumerate() {
+            lines.push(format!(
+                "{}. {} **{}**: {}",
+                index + 1,
+                entry.verdict.emoji(),
+                entry.asker_name,
+                entry.question
+            ));
+            lines.push(format!("   > {}", entry.reply.replace('\n', " ")));
+        }
+        if !self.hints.is_empty() {
+            lines.push(String::new());
+            lines.push(format!("## {}", t(locale, "transcript-hints")));
+            lines.push(String::new());
+            for (index, hint) in self.hints.iter().enumerate() {
+                lines.push(format!("{}. {}", index + 1, hint));
+            }
+        }
+        if let Some(solution) = &self.solution {
+            lines.push(String::new());
+            lines.push(format!("## {}", t(locale, "result-story")));
+            lines.push(String::new());
+            lines.push(solution.clone());
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    pub fn ending_label(&self, locale: Locale) -> String {
+        match &self.ending {
+            Some(Ending::Solved { solver }) => t_args(
+                locale,
+                "transcript-ending-solved",
+                &fluent_args!["name" => solver.clone()],
+            ),
+            Some(Ending::GaveUp) => t(locale, "transcript-ending-gaveup"),
+            Some(Ending::TimeUp) => t(locale, "transcript-ending-timeup"),
+            None => t(locale, "transcript-ending-playing"),
+        }
+    }
+
+    // このチャンネルで出題した問題か、このチャンネルから作ったスレッドで出題した問題か
+    pub fn is_visible_from(&self, channel_id: ChannelId) -> bool {
+        self.channel_id == channel_id || self.parent_channel == Some(channel_id)
+    }
+
+    pub fn summary(&self) -> Summary {
+        let count = |verdict: Verdict| {
+            self.questions
+                .iter()
+                .filter(|entry| entry.verdict == verdict)
+                .count()
+        };
+
+        let mut askers: Vec<(String, usize)> = vec![];
+        for entry in &self.questions {
+            match askers
+                .iter_mut()
+                .find(|(name, _)| *name == entry.asker_name)
+            {
+                Some((_, count)) => *count += 1,
+                None => askers.push((entry.asker_name.clone(), 1)),
+            }
+        }
+        askers.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+        let finished_at = self.finished_at.unwrap_or_else(|| {
+            self.questions
+                .last()
+                .map_or(self.started_at, |entry| entry.asked_at)
+        });
+        Summary {
+            yes: count(Verdict::Yes),
+            no: count(Verdict::No),
+            irrelevant: count(Verdict::Irrelevant),
+            askers,
+            duration: (finished_at - self.started_at).max(0),
+        }
+    }
+}
+
+pub struct Transcripts {
+    transcripts: Mutex<Vec<Transcript>>,
+    store: Store,
+}
+
+impl Transcripts {
+    pub fn new(store: Store) -> Self {
+        Self {
+            transcripts: Mutex::new(store.load("transcripts")),
+            store,
+        }
+    }
+
+    // 終わった問題の記録を保存し、IDを返す
+    pub async fn archive(&self, channel_id: ChannelId, session: &Session, ending: Ending) -> u64 {
+        let mut transcripts = self.transcripts.lock().await;
+        let id = next_id(&transcripts);
+        transcripts.push(Transcript::from_session(
+            id,
+            channel_id,
+            session,
+            Some(ending),
+        ));
+        if transcripts.len() > TRANSCRIPT_LIMIT {
+            let overflow = transcripts.len() - TRANSCRIPT_LIMIT;
+            transcripts.drain(..overflow);
+        }
+
+        self.save(&transcripts);
+        id
+    }
+
+    pub async fn get(&self, id: u64) -> Option<Transcript> {
+        self.transcripts
+            .lock()
+            .await
+            .iter()
+            .find(|transcript| transcript.id == id)
+            .cloned()
+    }
+
+    // チャンネル（とそのスレッド）の新しい順の記録
+    pub async fn recent(&self, channel_id: ChannelId, limit: usize) -> Vec<Transcript> {
+        self.transcripts
+            .lock()
+            .await
+            .iter()
+            .rev()
+            .filter(|transcript| transcript.is_visible_from(channel_id))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    fn save(&self, transcripts: &[Transcript]) {
+        if let Err(e) = self.store.save("transcripts", &transcripts) {
+            error!("トランスクリプトの保存に失敗しました: {:?}", e);
+        }
+    }
+}
+
+fn next_id(transcripts: &[Transcript]) -> u64 {
+    transcripts
+        .iter()
+        .map(|transcript| transcript.id)
+        .max()
+        .unwrap_or(0)
+        + 1
+}
@@ -0,0 +1,97 @@
+use fluent::fluent_args;
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+
+use crate::daily::{DailyPuzzle, DailyStanding};
+use crate::i18n::{t, t_args, Locale};
+
+// 結果ボードに表示する人数
+const BOARD_SIZE: usize = 20;
+
+// その日の問題の投稿
+pub fn daily_embed(puzzle: &DailyPuzzle) -> CreateEmbed {
+    let locale = puzzle.locale;
+    let title = t_args(
+        locale,
+        "daily-title",
+        &fluent_args!["date" => puzzle.date.to_string()],
+    );
+
+    CreateEmbed::new()
+        .color(0xff8c00)
+        .title(title)
+        .description(puzzle.problem.clone())
+        .footer(CreateEmbedFooter::new(t(locale, "daily-footer")))
+}
+
+// 前日の問題のストーリーと結果ボード
+pub fn daily_reveal_embed(
+    puzzle: &DailyPuzzle,
+    story: &str,
+    standings: &[DailyStanding],
+) -> CreateEmbed {
+    let locale = puzzle.locale;
+    let title = t_args(
+        locale,
+        "daily-reveal-title",
+        &fluent_args!["date" => puzzle.date.to_string()],
+    );
+
+    CreateEmbed::new()
+        .color(0x00ff00)
+        .title(title)
+        .description(format!(
+            "{}\n\n{}\n{}",
+            puzzle.problem,
+            t(locale, "result-story"),
+            story
+        ))
+        .field(
+            t(locale, "daily-board-title"),
+            board_lines(standings, locale),
+            false,
+        )
+}
+
+// その日の結果ボード
+pub fn daily_board_embed(
+    puzzle: &DailyPuzzle,
+    standings: &[DailyStanding],
+    locale: Locale,
+) -> CreateEmbed {
+    let title = t_args(
+        locale,
+        "daily-board-heading",
+        &fluent_args!["date" => puzzle.date.to_string()],
+    );
+
+    CreateEmbed::new()
+        .color(0xffd700)
+        .title(title)
+        .description(board_lines(standings, locale))
+}
+
+fn board_lines(standings: &[DailyStanding], locale: Locale) -> String {
+    if standings.is_empty() {
+        return t(locale, "daily-board-empty");
+    }
+
+    standings
+        .iter()
+        .take(BOARD_SIZE)
+        .enumerate()
+        .map(|(i, standing)| {
+            t_args(
+                locale,
+                "daily-board-line",
+                &fluent_args![
+                    "rank" => i + 1,
+                    "name" => standing.user_name.clone(),
+                    "questions" => standing.questions,
+                    "hints" => standing.hints,
+                    "streak" => standing.streak
+                ],
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
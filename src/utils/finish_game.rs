@@ -0,0 +1,253 @@
+use fluent::fluent_args;
+use serenity::all::{ChannelId, CommandInteraction, User};
+use serenity::builder::{
+    CreateActionRow, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage,
+    CreateMessage,
+};
+use serenity::prelude::*;
+use tracing::error;
+
+use crate::daily::DailyResult;
+use crate::game::Player;
+use crate::handlers::Bot;
+use crate::i18n::{t, t_args, Locale};
+use crate::models::{Session, SoloKind};
+use crate::transcript::Ending;
+use crate::utils::result_message::{result_buttons, result_embed, Outcome};
+use crate::utils::team_game::{close_team_thread, finish_team_game};
+
+// 問題の終わり方
+#[derive(Clone, Copy)]
+pub enum Finish<'a> {
+    // 正解した人
+    Solved(&'a User),
+    // ギブアップした人
+    GaveUp(&'a User),
+    TimeUp,
+}
+
+// 結果を表示する場所
+#[derive(Clone, Copy)]
+pub enum Reply<'a> {
+    // スラッシュコマンドへの返答。ソロモード・デイリーパズルでは本人にだけ表示する
+    Command(&'a CommandInteraction),
+    // チャンネルへの投稿（チャットモードでの正解・時間切れ）
+    Channel,
+}
+
+// 表示する結果
+enum Body {
+    Text(String),
+    Embed(Box<CreateEmbed>, Vec<CreateActionRow>),
+}
+
+// 正解・ギブアップ・時間切れになったゲームの結果を表示し、成績を記録する
+// 呼び出し元はゲームを Solved / GaveUp / TimedOut に遷移させてから呼ぶ
+// 結果を表示できなかった場合は得点とゲームを元に戻してfalseを返す。もう一度回答・ギブアップできる
+// 記録は二重に数えないよう、結果を表示できてから行う
+pub async fn finish_game(
+    ctx: &Context,
+    bot: &Bot,
+    channel_id: ChannelId,
+    session: &mut Session,
+    finish: Finish<'_>,
+    reply: Reply<'_>,
+) -> bool {
+    let locale = session.game.locale;
+
+    // チーム戦のスレッドでの正解は、親チャンネルに全チームの結果をまとめて投稿する
+    if let (Finish::Solved(solver), Some(team)) = (&finish, session.team.clone()) {
+        let key = if finish_team_game(ctx, bot, channel_id, session, solver).await {
+            "team-won"
+        } else {
+            "team-too-late"
+        };
+        let body = Body::Text(t_args(locale, key, &fluent_args!["team" => team]));
+        post(ctx, channel_id, reply, body, false).await;
+        close_team_thread(ctx, bot, channel_id).await;
+        return true;
+    }
+
+    // 結果を表示できなかった場合に戻せるよう、加算する前の得点を残しておく
+    let scores = session.scores.clone();
+    let body = match session.solo {
+        Some(kind) => solo_body(bot, channel_id, session, &finish, kind, locale).await,
+        None => {
+            let outcome = match finish {
+                Finish::Solved(solver) => {
+                    Outcome::Solved(solver, bot.score(session, solver.id).await)
+                }
+                Finish::GaveUp(_) => Outcome::GaveUp,
+                Finish::TimeUp => Outcome::TimeUp,
+            };
+            let story = filter_story(bot, channel_id, session, locale).await;
+            let embed = result_embed(outcome, &story, session, locale);
+            Body::Embed(
+                Box::new(embed),
+                result_buttons(locale, session.game.puzzle_id),
+            )
+        }
+    };
+
+    if !post(ctx, channel_id, reply, body, session.solo.is_some()).await {
+        session.scores = scores;
+        session.game.rollback();
+        return false;
+    }
+    let _ = session.game.reveal();
+
+    match session.solo {
+        Some(SoloKind::Daily(date)) => {
+            let (user, solved) = match finish {
+                Finish::Solved(user) => (user, true),
+                Finish::GaveUp(user) => (user, false),
+                Finish::TimeUp => return true,
+            };
+            bot.daily
+                .record(DailyResult {
+                    date,
+                    user_id: user.id,
+                    user_name: Player::from(user).name,
+                    questions: session.game.qa_log.len(),
+                    hints: session.game.hints.len(),
+                    solved,
+                })
+                .await;
+        }
+        // ひとりで遊んだ問題はライブラリとトランスクリプトに残さない
+        Some(SoloKind::Practice) => match finish {
+            Finish::Solved(solver) => bot.record_solve(channel_id, solver, session).await,
+            Finish::GaveUp(user) => {
+                bot.update_solo_stats(user.id, |stats| stats.gave_up += 1)
+                    .await
+            }
+            Finish::TimeUp => {}
+        },
+        None => {
+            let ending = match finish {
+                Finish::Solved(solver) => {
+                    bot.record_solve(channel_id, solver, session).await;
+                    Ending::Solved {
+                        solver: Player::from(solver).name,
+                    }
+                }
+                Finish::GaveUp(_) | Finish::TimeUp => {
+                    // DMでは時間切れもギブアップとして個人の成績に数える
+                    if let Some(user_id) = session.dm_user {
+                        bot.update_solo_stats(user_id, |stats| stats.gave_up += 1)
+                            .await;
+                    }
+                    match finish {
+                        Finish::TimeUp => Ending::TimeUp,
+                        _ => Ending::GaveUp,
+                    }
+                }
+            };
+            bot.library
+                .record_game(session, matches!(ending, Ending::Solved { .. }))
+                .await;
+            bot.transcripts.archive(channel_id, session, ending).await;
+        }
+    }
+    true
+}
+
+// ソロモード・デイリーパズルの結果。デイリーパズルのストーリーは翌日まで公開しない
+async fn solo_body(
+    bot: &Bot,
+    channel_id: ChannelId,
+    session: &Session,
+    finish: &Finish<'_>,
+    kind: SoloKind,
+    locale: Locale,
+) -> Body {
+    let questions = session.game.qa_log.len();
+    let hints = session.game.hints.len();
+
+    match (kind, finish) {
+        (SoloKind::Daily(date), Finish::Solved(solver)) => {
+            let streak = bot.daily.streak_if_solved(solver.id, date).await;
+            Body::Text(t_args(
+                locale,
+                "daily-solved",
+                &fluent_args!["questions" => questions, "streak" => streak],
+            ))
+        }
+        (SoloKind::Daily(_), _) => Body::Text(t(locale, "daily-gave-up")),
+        (SoloKind::Practice, finish) => {
+            let heading = match finish {
+                Finish::Solved(_) => t_args(
+                    locale,
+                    "solo-solved",
+                    &fluent_args!["questions" => questions, "hints" => hints],
+                ),
+                Finish::GaveUp(_) => t(locale, "result-giveup"),
+                Finish::TimeUp => t(locale, "result-timeup"),
+            };
+            let story = filter_story(bot, channel_id, session, locale).await;
+            let embed = CreateEmbed::new().color(0x00ff00).description(format!(
+                "{}\n\n{}\n{}",
+                heading,
+                t(locale, "result-story"),
+                story
+            ));
+            Body::Embed(Box::new(embed), vec![])
+        }
+    }
+}
+
+async fn filter_story(
+    bot: &Bot,
+    channel_id: ChannelId,
+    session: &Session,
+    locale: Locale,
+) -> String {
+    bot.moderation
+        .filter_story(
+            &bot.openai_client,
+            session.game.solution.clone(),
+            channel_id,
+            locale,
+        )
+        .await
+}
+
+// 結果を表示できたかどうかを返す
+async fn post(
+    ctx: &Context,
+    channel_id: ChannelId,
+    reply: Reply<'_>,
+    body: Body,
+    ephemeral: bool,
+) -> bool {
+    let result = match reply {
+        Reply::Command(command) => {
+            let data = match body {
+                Body::Text(content) => CreateInteractionResponseMessage::new().content(content),
+                Body::Embed(embed, components) => CreateInteractionResponseMessage::new()
+                    .embed(*embed)
+                    .components(components),
+            };
+            let builder = CreateInteractionResponse::Message(data.ephemeral(ephemeral));
+            command.create_response(&ctx.http, builder).await
+        }
+        Reply::Channel => {
+            let builder = match body {
+                Body::Text(content) => CreateMessage::new().content(content),
+                Body::Embed(embed, components) => {
+                    CreateMessage::new().embed(*embed).components(components)
+                }
+            };
+            channel_id
+                .send_message(&ctx.http, builder)
+                .await
+                .map(|_| ())
+        }
+    };
+
+    if let Err(why) = result {
+        error!("結果の表示に失敗しました: {}", why);
+        return false;
+    }
+    true
+}
@@ -0,0 +1,93 @@
+use fluent::fluent_args;
+use serenity::all::ChannelId;
+use serenity::builder::{CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter};
+use serenity::model::application::ButtonStyle;
+
+use crate::i18n::{t, t_args, Locale};
+use crate::leaderboard::{Leaderboard, Period, Standing};
+
+// 1ページに表示する人数
+const PAGE_SIZE: usize = 10;
+
+// ランキングの指定したページの埋め込みとページ送りボタン
+// セッションの場合は `channel_id` のチャンネルの `session_started` 以降の記録を集計する
+pub async fn leaderboard_message(
+    leaderboard: &Leaderboard,
+    period: Period,
+    page: usize,
+    channel_id: ChannelId,
+    session_started: i64,
+    locale: Locale,
+) -> (CreateEmbed, Option<CreateActionRow>) {
+    let from = leaderboard.period_start(period, session_started);
+    let channel_id = (period == Period::Session).then_some(channel_id);
+    let standings = leaderboard.standings(from, i64::MAX, channel_id).await;
+
+    let pages = standings.len().div_ceil(PAGE_SIZE).max(1);
+    let page = page.min(pages - 1);
+    let title = t(locale, &format!("leaderboard-title-{}", period.as_str()));
+    let embed = leaderboard_embed(&standings, title, page, pages, locale);
+
+    let buttons = (pages > 1).then(|| {
+        let previous = CreateButton::new(format!(
+            "leaderboard:{}:{}",
+            period.as_str(),
+            page.saturating_sub(1)
+        ))
+        .label("◀")
+        .style(ButtonStyle::Secondary)
+        .disabled(page == 0);
+        let next = CreateButton::new(format!("leaderboard:{}:{}", period.as_str(), page + 1))
+            .label("▶")
+            .style(ButtonStyle::Secondary)
+            .disabled(page + 1 >= pages);
+        CreateActionRow::Buttons(vec![previous, next])
+    });
+
+    (embed, buttons)
+}
+
+pub fn leaderboard_embed(
+    standings: &[Standing],
+    title: String,
+    page: usize,
+    pages: usize,
+    locale: Locale,
+) -> CreateEmbed {
+    let description = if standings.is_empty() {
+        t(locale, "leaderboard-empty")
+    } else {
+        standings
+            .iter()
+            .enumerate()
+            .skip(page * PAGE_SIZE)
+            .take(PAGE_SIZE)
+            .map(|(i, standing)| {
+                t_args(
+                    locale,
+                    "leaderboard-line",
+                    &fluent_args![
+                        "rank" => i + 1,
+                        "name" => standing.user_name.clone(),
+                        "solves" => standing.solves,
+                        "average" => format!("{:.1}", standing.average_questions),
+                        "streak" => standing.streak
+                    ],
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let footer = t_args(
+        locale,
+        "leaderboard-footer",
+        &fluent_args!["page" => page + 1, "pages" => pages],
+    );
+
+    CreateEmbed::new()
+        .color(0xffd700)
+        .title(title)
+        .description(description)
+        .footer(CreateEmbedFooter::new(footer))
+}
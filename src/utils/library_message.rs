@@ -0,0 +1,42 @@
+use fluent::fluent_args;
+use serenity::builder::CreateEmbed;
+
+use crate::i18n::{t, t_args, Locale};
+use crate::library::LibraryPuzzle;
+
+// 一覧に表示する問題文の長さ
+const PROBLEM_PREVIEW: usize = 60;
+
+// 評価の高い問題の一覧
+pub fn library_embed(puzzles: &[LibraryPuzzle], locale: Locale) -> CreateEmbed {
+    let description = if puzzles.is_empty() {
+        t(locale, "library-empty")
+    } else {
+        puzzles
+            .iter()
+            .map(|puzzle| {
+                let mut problem: String = puzzle.problem.chars().take(PROBLEM_PREVIEW).collect();
+                if puzzle.problem.chars().count() > PROBLEM_PREVIEW {
+                    problem.push('…');
+                }
+                t_args(
+                    locale,
+                    "library-line",
+                    &fluent_args![
+                        "id" => puzzle.id,
+                        "stars" => format!("{:.1}", puzzle.average_stars().unwrap_or_default()),
+                        "count" => puzzle.ratings.len(),
+                        "rate" => format!("{:.0}", puzzle.solve_rate().unwrap_or_default() * 100.0),
+                        "problem" => problem.replace('\n', " ")
+                    ],
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    CreateEmbed::new()
+        .color(0xffd700)
+        .title(t(locale, "library-title"))
+        .description(description)
+}
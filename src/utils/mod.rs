@@ -1 +1,14 @@
+pub mod daily_message;
+pub mod finish_game;
+pub mod leaderboard_message;
+pub mod library_message;
+pub mod qa_board;
 pub mod question_generator;
+pub mod question_message;
+pub mod result_message;
+pub mod scheduler;
+pub mod submission_message;
+pub mod team_game;
+pub mod thread;
+pub mod timer;
+pub mod turn;
@@ -0,0 +1,101 @@
+use fluent::fluent_args;
+use serenity::all::{ChannelId, UserId};
+use serenity::builder::{CreateEmbed, CreateEmbedFooter, CreateMessage, EditMessage};
+use serenity::prelude::*;
+use tracing::error;
+
+use crate::constants::prompt::{game_prompt, GamePrompt};
+use crate::i18n::{t, t_args};
+use crate::models::Session;
+
+// 埋め込みの説明文の上限（4096文字）に収まるようにする
+const DESCRIPTION_LIMIT: usize = 4000;
+const HINT_LENGTH: usize = 60;
+
+// 問題文と質問の一覧をまとめたQ&Aボード
+// 上限を超える場合は古い質問から省略する
+pub fn board_embed(session: &Session) -> CreateEmbed {
+    let locale = session.game.locale;
+    let prompt = game_prompt(locale);
+    let header = format!("{}\n\n", session.game.problem);
+
+    let mut lines = vec![];
+    let mut length = header.chars().count();
+    for (i, entry) in session.game.qa_log.iter().enumerate().rev() {
+        let mut line = format!(
+            "{} **Q{}.** {} ({})",
+            entry.verdict.emoji(),
+            i + 1,
+            entry.question,
+            UserId::from(entry.asker).mention(),
+        );
+        let hint = short_hint(&entry.reply, prompt);
+        if !hint.is_empty() {
+            line.push_str(&format!("\n> {}", hint));
+        }
+        length += line.chars().count() + 1;
+        if length > DESCRIPTION_LIMIT {
+            break;
+        }
+        lines.push(line);
+    }
+    lines.reverse();
+
+    let footer = t_args(
+        locale,
+        "board-footer",
+        &fluent_args!["count" => session.game.qa_log.len()],
+    );
+
+    let mut embed = CreateEmbed::new()
+        .color(0x0099ff)
+        .title(t(locale, "board-title"))
+        .description(header + &lines.join("\n"))
+        .footer(CreateEmbedFooter::new(footer));
+
+    if let Some(user) = session.current_turn() {
+        embed = embed.field(t(locale, "turn-title"), user.mention().to_string(), false);
+    }
+
+    embed
+}
+
+// Q&Aボードを更新する。まだ投稿していない場合や編集に失敗した場合は新しく投稿する
+pub async fn update_board(ctx: &Context, channel_id: ChannelId, session: &mut Session) {
+    let embed = board_embed(session);
+
+    if let Some(board) = session.board {
+        let builder = EditMessage::new().embed(embed.clone());
+        match channel_id.edit_message(&ctx.http, board, builder).await {
+            Ok(_) => return,
+            Err(why) => error!("Q&Aボードの更新に失敗しました: {}", why),
+        }
+    }
+
+    match channel_id
+        .send_message(&ctx.http, CreateMessage::new().embed(embed))
+        .await
+    {
+        Ok(message) => session.board = Some(message.id),
+        Err(why) => error!("Q&Aボードの投稿に失敗しました: {}", why),
+    }
+}
+
+// 返答からはい・いいえを除いた短いヒント
+pub fn short_hint(reply: &str, prompt: &GamePrompt) -> String {
+    let reply = reply.trim();
+    let rest = prompt
+        .yes
+        .iter()
+        .chain(prompt.no)
+        .find_map(|word| reply.strip_prefix(word))
+        .unwrap_or(reply)
+        .trim_start_matches(|c: char| c.is_whitespace() || "、。,.!！".contains(c));
+
+    if rest.chars().count() > HINT_LENGTH {
+        let truncated: String = rest.chars().take(HINT_LENGTH).collect();
+        format!("{}…", truncated)
+    } else {
+        rest.to_string()
+    }
+}
@@ -1,36 +1,163 @@
+use std::sync::Arc;
+
+use crate::api::OpenAIClient;
+use crate::constants::prompt::game_prompt;
+use crate::critique::{critique, Critic, Rejected, Review};
+use crate::game::Game;
 use crate::handlers::Bot;
-use crate::models::{ChatCompletionMessage, Role};
-
-use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
-
-pub async fn generate_question_builder(bot: &Bot) -> CreateInteractionResponse {
-    bot.reset_messages().await;
-    bot.messages.lock().await.push(ChatCompletionMessage::new(
-        Role::User,
-        "新しい問題を出題してください。".to_string(),
-    ));
-    let response = bot
-        .openai_client
-        .send_request(&bot.messages.lock().await)
-        .await;
-
-    let mut message = "問題です\n".to_string();
-
-    if let Ok(res) = response {
-        bot.messages
-            .lock()
-            .await
-            .push(ChatCompletionMessage::new(Role::Assistant, res.to_string()));
+use crate::i18n::{t_args, Locale};
+use crate::library::Library;
+use crate::models::{ChannelSettings, Session};
+use crate::moderation::{Blocked, Moderation, ModerationAction, Target};
 
-        message.push_str(&res);
+use fluent::fluent_args;
+use serenity::all::ChannelId;
+use tracing::{info, warn};
 
-        let data = CreateInteractionResponseMessage::new().content(message);
+// 問題の生成に使うもの。Botの外（定期投稿など）からも生成できるようにまとめる
+#[derive(Clone)]
+pub struct Generator {
+    pub client: OpenAIClient,
+    pub moderation: Arc<Moderation>,
+    pub library: Arc<Library>,
+    pub critic: Arc<Critic>,
+    // モデレーション・重複・批評で作り直しになった場合に出題をやり直す回数
+    pub attempts: usize,
+}
 
-        CreateInteractionResponse::Message(data)
+// 会話履歴をリセットして新しい問題を出題させる
+// 問題文と解答は分けて保存し、表示するのは問題文だけにする
+// 生成した問題がモデレーションに該当した場合は設定に応じて作り直すか、Blockedを返す
+// 生成した問題はライブラリに保存し、チャンネルの設定によってはライブラリの問題を再利用する
+pub async fn generate_question(
+    bot: &Bot,
+    session: &mut Session,
+    locale: Locale,
+    channel_id: ChannelId,
+) -> Result<String, anyhow::Error> {
+    let system_prompt = bot.system_prompt(locale).await;
+    let reused = if session.settings.reuse_library {
+        bot.library
+            .pick(channel_id, locale, session.settings.difficulty)
+            .await
     } else {
-        let data = CreateInteractionResponseMessage::new()
-            .content("APIの返却値取得においてエラーが発生しました".to_string());
+        None
+    };
+
+    session.reset_messages(locale, system_prompt.clone());
+    let problem = match reused {
+        // ライブラリの問題を出題する場合は生成しない
+        Some(puzzle) => {
+            session.game.problem = puzzle.problem.clone();
+            session.game.solution = puzzle.solution;
+            session.game.puzzle_id = Some(puzzle.id);
+            session.game.author_hints = puzzle.hints;
+            match (puzzle.title, puzzle.author) {
+                // 投稿された問題は作者の名前を添えて表示する
+                (Some(title), Some(author)) => format!(
+                    "{}\n{}",
+                    puzzle.problem,
+                    t_args(
+                        locale,
+                        "submission-credit",
+                        &fluent_args!["title" => title, "author" => author],
+                    )
+                ),
+                _ => puzzle.problem,
+            }
+        }
+        None => {
+            let problem = generate_puzzle(
+                &bot.generator(),
+                system_prompt,
+                &mut session.game,
+                &session.settings,
+                channel_id,
+            )
+            .await?;
+            bot.library
+                .add_generated(&mut session.game, session.settings.difficulty, channel_id)
+                .await;
+            problem
+        }
+    };
+    Ok(problem)
+}
+
+// Botを介さずに問題を生成する。デイリーパズルやターミナル版のようにイベントの外から出題する場合に使う
+// 問題はゲームのロケールで、設定の難易度とテーマに合わせて生成する
+// 出題済みの問題やプロンプトの例とほぼ同じ問題や、批評で不合格になった問題は作り直す
+// 最後の試行で重複した場合はそのまま出題するが、批評で不合格になった問題は出題せずRejectedを返す
+pub async fn generate_puzzle(
+    generator: &Generator,
+    system_prompt: String,
+    game: &mut Game,
+    settings: &ChannelSettings,
+    channel_id: ChannelId,
+) -> Result<String, anyhow::Error> {
+    let client = &generator.client;
+    let locale = game.locale;
+    let attempts = generator.attempts.max(1);
+    // 最後の試行が批評で不合格になったかどうか
+    let mut rejected = false;
+    for attempt in 1..=attempts {
+        rejected = false;
+        game.reset(locale, system_prompt.clone());
+        let prompt = game_prompt(locale);
+        let request = prompt.new_question_request(settings.difficulty, settings.theme.as_deref());
+        let reply = game.request(client, request).await?;
+        let (problem, solution) = prompt.split_puzzle(&reply);
+        let solution = match solution {
+            Some(solution) => solution,
+            None => game
+                .request(client, prompt.solution_request.to_string())
+                .await?
+                .trim()
+                .to_string(),
+        };
+
+        match generator
+            .moderation
+            .screen(client, Target::Puzzle, &problem, channel_id, None)
+            .await
+        {
+            Some(ModerationAction::Regenerate) => continue,
+            Some(ModerationAction::Block) => return Err(Blocked.into()),
+            _ => {}
+        }
+
+        if attempt < attempts
+            && generator
+                .library
+                .is_duplicate(&problem, locale, &system_prompt, channel_id)
+                .await
+        {
+            info!("出題済みの問題と重複したため作り直します: {}", problem);
+            continue;
+        }
+
+        // 批評に失敗した場合はゲームを止めないよう合格として扱う
+        match critique(client, prompt, &problem, &solution).await {
+            Ok(Review::Rejected(reason)) => {
+                info!("批評で不合格になりました: {}", reason);
+                generator
+                    .critic
+                    .reject(locale, &problem, &solution, reason)
+                    .await;
+                rejected = true;
+                continue;
+            }
+            Ok(Review::Accepted) => {}
+            Err(e) => warn!("問題の批評に失敗しました: {:?}", e),
+        }
+
+        game.problem = problem.clone();
+        game.solution = solution;
+        return Ok(problem);
+    }
 
-        CreateInteractionResponse::Message(data)
+    if rejected {
+        return Err(Rejected.into());
     }
+    Err(Blocked.into())
 }
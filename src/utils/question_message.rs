@@ -0,0 +1,23 @@
+use serenity::builder::EditInteractionResponse;
+
+use crate::critique::Rejected;
+use crate::i18n::{t, Locale};
+use crate::moderation::Blocked;
+
+// 出題結果を表示用のメッセージにする
+pub fn question_message(question: &Result<String, anyhow::Error>, locale: Locale) -> String {
+    match question {
+        Ok(res) => format!("{}\n{}", t(locale, "problem-header"), res),
+        Err(e) if e.downcast_ref::<Blocked>().is_some() => t(locale, "moderation-puzzle-blocked"),
+        Err(e) if e.downcast_ref::<Rejected>().is_some() => t(locale, "puzzle-rejected"),
+        Err(_) => t(locale, "api-error"),
+    }
+}
+
+// 問題の生成は時間がかかるため、スラッシュコマンド・ボタンへの返答は保留しておき、出題結果で書き換える
+pub fn question_response(
+    question: &Result<String, anyhow::Error>,
+    locale: Locale,
+) -> EditInteractionResponse {
+    EditInteractionResponse::new().content(question_message(question, locale))
+}
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use fluent::fluent_args;
+use serenity::builder::{CreateActionRow, CreateButton, CreateEmbed};
+use serenity::model::application::ButtonStyle;
+use serenity::model::id::UserId;
+use serenity::model::user::User;
+use serenity::prelude::*;
+
+use crate::i18n::{t, t_args, Locale};
+use crate::library::Feedback;
+use crate::models::Session;
+use crate::scoring::{Award, ScoreItem};
+
+// 問題の終わり方
+pub enum Outcome<'a> {
+    // 正解した人と、参加者ごとの得点
+    Solved(&'a User, Vec<Award>),
+    GaveUp,
+    TimeUp,
+}
+
+// 「次の問題に進む」「終了する」ボタンと、ライブラリに記録した問題の評価ボタン
+pub fn result_buttons(locale: Locale, puzzle_id: Option<u64>) -> Vec<CreateActionRow> {
+    let next_button = CreateButton::new("next_button")
+        .label(t(locale, "result-next-button"))
+        .style(ButtonStyle::Primary);
+
+    let cancel_button = CreateButton::new("cancel_button")
+        .label(t(locale, "result-finish-button"))
+        .style(ButtonStyle::Danger);
+
+    let mut rows = vec![CreateActionRow::Buttons(vec![next_button, cancel_button])];
+    if let Some(id) = puzzle_id {
+        rows.extend(rating_buttons(id, locale));
+    }
+    rows
+}
+
+// 評価ボタン（rate:<問題のID>:<星の数か評価>）
+fn rating_buttons(id: u64, locale: Locale) -> Vec<CreateActionRow> {
+    let stars = (1..=5)
+        .map(|stars| {
+            CreateButton::new(format!("rate:{}:{}", id, stars))
+                .label("★".repeat(stars))
+                .style(ButtonStyle::Secondary)
+        })
+        .collect();
+    let feedback = Feedback::ALL
+        .iter()
+        .map(|feedback| {
+            CreateButton::new(format!("rate:{}:{}", id, feedback.as_str()))
+                .label(t(locale, &format!("rating-{}", feedback.as_str())))
+                .style(ButtonStyle::Secondary)
+        })
+        .collect();
+
+    vec![
+        CreateActionRow::Buttons(stars),
+        CreateActionRow::Buttons(feedback),
+    ]
+}
+
+// 正解・ギブアップ・時間切れ時の結果の埋め込み。正解した場合は得点を加算して内訳を表示する
+pub fn result_embed(
+    outcome: Outcome,
+    description: &str,
+    session: &mut Session,
+    locale: Locale,
+) -> CreateEmbed {
+    let mut breakdown = None;
+    let mut message = match outcome {
+        Outcome::Solved(user, awards) => {
+            for award in &awards {
+                *session.scores.entry(award.user_id).or_insert(0) += award.points;
+            }
+            breakdown = Some(breakdown_field(&awards, locale));
+
+            format!(
+                "{}\n\n",
+                t_args(
+                    locale,
+                    "result-correct",
+                    &fluent_args!["user" => user.mention().to_string()]
+                )
+            )
+        }
+        Outcome::GaveUp => format!("{}\n", t(locale, "result-giveup")),
+        Outcome::TimeUp => format!("{}\n", t(locale, "result-timeup")),
+    };
+    message.push_str(&format!("{}\n{}", t(locale, "result-story"), description));
+
+    let mut embed = CreateEmbed::new().color(0x00ff00).description(message);
+
+    if let Some(breakdown) = breakdown {
+        embed = embed.field(t(locale, "score-breakdown"), breakdown, false);
+    }
+
+    let fields = score_fields(&session.scores, locale);
+    if !fields.is_empty() {
+        embed = embed.fields(fields);
+    }
+
+    embed
+}
+
+// 参加者ごとの得点の内訳
+fn breakdown_field(awards: &[Award], locale: Locale) -> String {
+    awards
+        .iter()
+        .map(|award| {
+            let items = award
+                .items
+                .iter()
+                .map(|item| match *item {
+                    ScoreItem::Solved(points) => {
+                        t_args(locale, "score-solved", &fluent_args!["points" => points])
+                    }
+                    ScoreItem::Questions(points, count) => t_args(
+                        locale,
+                        "score-questions",
+                        &fluent_args!["points" => points, "count" => count],
+                    ),
+                    ScoreItem::Time(points, minutes) => t_args(
+                        locale,
+                        "score-time",
+                        &fluent_args!["points" => points, "minutes" => minutes],
+                    ),
+                    ScoreItem::Hints(points, count) => t_args(
+                        locale,
+                        "score-hints",
+                        &fluent_args!["points" => points, "count" => count],
+                    ),
+                    ScoreItem::Assist(points, count) => t_args(
+                        locale,
+                        "score-assist",
+                        &fluent_args!["points" => points, "count" => count],
+                    ),
+                    ScoreItem::WrongAnswers(points, count) => t_args(
+                        locale,
+                        "score-wrong",
+                        &fluent_args!["points" => points, "count" => count],
+                    ),
+                })
+                .collect::<Vec<_>>()
+                .join(" / ");
+
+            format!(
+                "{} **+{}** ({})",
+                award.user_id.mention(),
+                award.points,
+                items
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// 得点の高い順に並べた埋め込みのフィールド
+pub fn score_fields(scores: &HashMap<UserId, u32>, locale: Locale) -> Vec<(String, String, bool)> {
+    let mut sorted_scores: Vec<_> = scores.iter().collect();
+    sorted_scores.sort_by(|a, b| b.1.cmp(a.1));
+
+    sorted_scores
+        .iter()
+        .enumerate()
+        .map(|(i, (user, score))| {
+            (
+                t_args(
+                    locale,
+                    "result-score",
+                    &fluent_args!["rank" => i + 1, "points" => **score],
+                ),
+                user.mention().to_string(),
+                true,
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use serenity::all::{ChannelId, Http};
+use serenity::builder::CreateMessage;
+use tokio::sync::RwLock;
+use tracing::{error, warn};
+
+use crate::daily::{Daily, DailyPuzzle};
+use crate::i18n::{t, Locale};
+use crate::leaderboard::Leaderboard;
+use crate::models::{ChannelSettings, Session};
+use crate::utils::daily_message::{daily_embed, daily_reveal_embed};
+use crate::utils::leaderboard_message::leaderboard_embed;
+use crate::utils::question_generator::{generate_puzzle, Generator};
+
+// デイリーパズルの生成に失敗した場合にやり直すまでの時間
+const DAILY_RETRY: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+// 毎週月曜日の0時に、前の週のランキングを指定したチャンネルに投稿する
+pub fn start_weekly_leaderboard(
+    http: Arc<Http>,
+    leaderboard: Arc<Leaderboard>,
+    channel_id: ChannelId,
+    locale: Locale,
+) {
+    tokio::spawn(async move {
+        loop {
+            let next = leaderboard.next_week_start();
+            let wait = (next.with_timezone(&Utc) - Utc::now())
+                .to_std()
+                .unwrap_or_default();
+            tokio::time::sleep(wait).await;
+
+            let until = next.timestamp();
+            let from = (next - Duration::days(7)).timestamp();
+            let standings = leaderboard.standings(from, until, None).await;
+            let title = t(locale, "leaderboard-title-last-week");
+            let embed = leaderboard_embed(&standings, title, 0, 1, locale);
+
+            if let Err(why) = channel_id
+                .send_message(&http, CreateMessage::new().embed(embed))
+                .await
+            {
+                error!("ランキングの投稿に失敗しました: {:?}", why);
+            }
+
+            // 投稿が早すぎて同じ週をもう一度待たないようにする
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+        }
+    });
+}
+
+// 毎日決まった時刻に、前日の問題のストーリーと結果を公開してから新しい問題を投稿する
+// 起動した時点で今日の問題がまだない場合はすぐに投稿する
+pub fn start_daily_puzzle(
+    http: Arc<Http>,
+    daily: Arc<Daily>,
+    generator: Generator,
+    system_prompts: Arc<RwLock<HashMap<Locale, String>>>,
+    channel_id: ChannelId,
+    settings: ChannelSettings,
+) {
+    tokio::spawn(async move {
+        loop {
+            // 生成中に日付が変わっても、投稿を始めた日の問題として記録する
+            let date = daily.today();
+            if daily.current().await.is_none() {
+                for puzzle in daily.unrevealed().await {
+                    let story = generator
+                        .moderation
+                        .filter_story(
+                            &generator.client,
+                            puzzle.solution.clone(),
+                            channel_id,
+                            puzzle.locale,
+                        )
+                        .await;
+                    let standings = daily.board(puzzle.date).await;
+                    let embed = daily_reveal_embed(&puzzle, &story, &standings);
+
+                    if let Err(why) = channel_id
+                        .send_message(&http, CreateMessage::new().embed(embed))
+                        .await
+                    {
+                        error!("デイリーパズルの結果の投稿に失敗しました: {:?}", why);
+                    }
+                    daily.mark_revealed(puzzle.date).await;
+                }
+
+                let locale = settings.locale.unwrap_or_default();
+                let system_prompt = system_prompts.read().await[&locale].clone();
+                let mut session = Session::new(settings.clone());
+                let problem = generate_puzzle(
+                    &generator,
+                    system_prompt,
+                    &mut session.game,
+                    &settings,
+                    channel_id,
+                )
+                .await;
+                let Ok(problem) = problem else {
+                    warn!("デイリーパズルの生成に失敗しました: {:?}", problem.err());
+                    tokio::time::sleep(DAILY_RETRY).await;
+                    continue;
+                };
+
+                let puzzle = DailyPuzzle {
+                    date,
+                    locale,
+                    problem,
+                    solution: session.game.solution.clone(),
+                    revealed: false,
+                };
+                // 投稿できなかった問題は記録せず、しばらくしてから作り直す
+                let builder = CreateMessage::new().embed(daily_embed(&puzzle));
+                if let Err(why) = channel_id.send_message(&http, builder).await {
+                    error!("デイリーパズルの投稿に失敗しました: {:?}", why);
+                    tokio::time::sleep(DAILY_RETRY).await;
+                    continue;
+                }
+                daily.add_puzzle(puzzle).await;
+
+                // 出題済みの問題として覚えておく
+                generator
+                    .library
+                    .add_generated(&mut session.game, settings.difficulty, channel_id)
+                    .await;
+            }
+
+            let wait = (daily.next_post_time().with_timezone(&Utc) - Utc::now())
+                .to_std()
+                .unwrap_or_default();
+            tokio::time::sleep(wait).await;
+        }
+    });
+}
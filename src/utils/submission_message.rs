@@ -0,0 +1,70 @@
+use fluent::fluent_args;
+use serenity::builder::{CreateActionRow, CreateButton, CreateEmbed};
+use serenity::model::application::ButtonStyle;
+
+use crate::i18n::{t, t_args, Locale};
+use crate::submissions::{Submission, SubmissionStatus};
+
+// 審査する投稿の内容と、承認・却下ボタン（review:<投稿のID>:<approve|reject>）
+pub fn review_message(
+    submission: &Submission,
+    locale: Locale,
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+    let approve = CreateButton::new(format!("review:{}:approve", submission.id))
+        .label(t(locale, "submission-approve-button"))
+        .style(ButtonStyle::Success);
+    let reject = CreateButton::new(format!("review:{}:reject", submission.id))
+        .label(t(locale, "submission-reject-button"))
+        .style(ButtonStyle::Danger);
+
+    (
+        review_embed(submission, locale),
+        vec![CreateActionRow::Buttons(vec![approve, reject])],
+    )
+}
+
+// 投稿の内容。審査済みの場合は結果も表示する
+pub fn review_embed(submission: &Submission, locale: Locale) -> CreateEmbed {
+    let (color, status) = match submission.status {
+        SubmissionStatus::Pending => (0x5865f2, "submission-status-pending"),
+        SubmissionStatus::Approved => (0x57f287, "submission-status-approved"),
+        SubmissionStatus::Rejected => (0xed4245, "submission-status-rejected"),
+    };
+    let hints = if submission.hints.is_empty() {
+        t(locale, "admin-config-none")
+    } else {
+        submission.hints.join("\n")
+    };
+
+    CreateEmbed::new()
+        .color(color)
+        .title(t_args(
+            locale,
+            "submission-review-title",
+            &fluent_args!["id" => submission.id, "title" => submission.title.clone()],
+        ))
+        .description(&submission.problem)
+        .field(
+            t(locale, "submission-author"),
+            &submission.author_name,
+            true,
+        )
+        .field(
+            t(locale, "admin-config-difficulty"),
+            t(
+                locale,
+                &format!(
+                    "choice-admin-config-difficulty-{}",
+                    submission.difficulty.as_str()
+                ),
+            ),
+            true,
+        )
+        .field(t(locale, "submission-status"), t(locale, status), true)
+        .field(
+            t(locale, "submission-solution"),
+            format!("||{}||", submission.solution),
+            false,
+        )
+        .field(t(locale, "submission-hints"), hints, false)
+}
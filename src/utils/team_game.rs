@@ -0,0 +1,154 @@
+use std::cmp::Reverse;
+
+use fluent::fluent_args;
+use serenity::all::ChannelId;
+use serenity::builder::{CreateEmbed, CreateMessage};
+use serenity::model::user::User;
+use serenity::prelude::*;
+use tracing::error;
+
+use crate::handlers::Bot;
+use crate::i18n::{t, t_args, Locale};
+use crate::models::{Session, Team};
+use crate::utils::thread::archive_thread;
+
+// チーム戦で正解したチームを勝者にして、他のチームのスレッドを終了する
+// 親チャンネルに全チームの結果をまとめて投稿する
+// 正解したチームのスレッドは、呼び出し元が返答してから `close_team_thread` で終了する
+// 他のチームが先に正解していた場合は何もせずfalseを返す
+pub async fn finish_team_game(
+    ctx: &Context,
+    bot: &Bot,
+    thread_id: ChannelId,
+    session: &mut Session,
+    solver: &User,
+) -> bool {
+    let (Some(team_name), Some(parent_channel)) = (session.team.clone(), session.parent_channel)
+    else {
+        return false;
+    };
+    let Some(parent) = bot.find_session(parent_channel).await else {
+        return false;
+    };
+
+    let awards = bot.score(session, solver.id).await;
+    let points: u32 = awards.iter().map(|award| award.points).sum();
+
+    // 勝者の決定は親チャンネルのセッションで行う
+    // 他のスレッドのセッションをロックする前に親のロックを外し、デッドロックしないようにする
+    let (teams, locale) = {
+        let mut parent = parent.lock().await;
+        if !parent.game.is_playing() || parent.game.finish().is_err() {
+            return false;
+        }
+        for award in &awards {
+            *parent.scores.entry(award.user_id).or_insert(0) += award.points;
+        }
+        if let Some(team) = parent.teams.iter_mut().find(|team| team.name == team_name) {
+            team.score += points;
+        }
+        (parent.teams.clone(), parent.game.locale)
+    };
+
+    let _ = session.game.finish();
+    bot.record_solve(thread_id, solver, session).await;
+
+    let mut questions = vec![];
+    for team in &teams {
+        let Some(thread) = team.thread else {
+            continue;
+        };
+        let count = if thread == thread_id {
+            session.game.qa_log.len()
+        } else if let Some(other) = bot.find_session(thread).await {
+            let mut other = other.lock().await;
+            let _ = other.game.finish();
+            other.game.qa_log.len()
+        } else {
+            0
+        };
+        questions.push((team, count));
+    }
+
+    let story = bot
+        .moderation
+        .filter_story(
+            &bot.openai_client,
+            session.game.solution.clone(),
+            parent_channel,
+            locale,
+        )
+        .await;
+    let embed = team_result_embed(&team_name, solver, &story, &questions, locale);
+    if let Err(why) = parent_channel
+        .send_message(&ctx.http, CreateMessage::new().embed(embed))
+        .await
+    {
+        error!("Error sending message: {:?}", why);
+    }
+
+    let finished = t_args(
+        locale,
+        "team-finished",
+        &fluent_args![
+            "team" => team_name.clone(),
+            "channel" => parent_channel.mention().to_string()
+        ],
+    );
+    for (team, _) in &questions {
+        let Some(thread) = team.thread.filter(|thread| *thread != thread_id) else {
+            continue;
+        };
+        if let Err(why) = thread.say(&ctx.http, finished.clone()).await {
+            error!("Error sending message: {:?}", why);
+        }
+        close_team_thread(ctx, bot, thread).await;
+    }
+
+    true
+}
+
+pub async fn close_team_thread(ctx: &Context, bot: &Bot, thread: ChannelId) {
+    archive_thread(ctx, thread).await;
+    bot.remove_session(thread).await;
+}
+
+// 勝ったチームとストーリー、各チームの得点と質問数
+fn team_result_embed(
+    winner: &str,
+    solver: &User,
+    story: &str,
+    questions: &[(&Team, usize)],
+    locale: Locale,
+) -> CreateEmbed {
+    let description = format!(
+        "{}\n\n{}\n{}",
+        t_args(
+            locale,
+            "team-result-winner",
+            &fluent_args!["team" => winner, "user" => solver.mention().to_string()],
+        ),
+        t(locale, "result-story"),
+        story
+    );
+
+    let mut teams: Vec<_> = questions.to_vec();
+    teams.sort_by_key(|(team, _)| Reverse(team.score));
+    let fields = teams.into_iter().map(|(team, count)| {
+        (
+            team.name.clone(),
+            t_args(
+                locale,
+                "team-result-value",
+                &fluent_args!["score" => team.score, "count" => count],
+            ),
+            true,
+        )
+    });
+
+    CreateEmbed::new()
+        .color(0x00ff00)
+        .title(t(locale, "team-result-title"))
+        .description(description)
+        .fields(fields)
+}
@@ -0,0 +1,45 @@
+use fluent::fluent_args;
+use serenity::all::ChannelId;
+use serenity::builder::{CreateEmbed, CreateMessage, EditThread};
+use serenity::prelude::*;
+use tracing::error;
+
+use crate::i18n::t_args;
+use crate::models::Session;
+use crate::utils::result_message::score_fields;
+
+// 親チャンネルに結果へのリンクを投稿し、スレッドをアーカイブしてロックする
+pub async fn close_thread(
+    ctx: &Context,
+    thread: ChannelId,
+    parent_channel: ChannelId,
+    session: &Session,
+) {
+    let locale = session.game.locale;
+    let summary = t_args(
+        locale,
+        "thread-summary",
+        &fluent_args!["thread" => thread.mention().to_string()],
+    );
+    let mut embed = CreateEmbed::new().color(0x00ff00).description(summary);
+
+    let fields = score_fields(&session.scores, locale);
+    if !fields.is_empty() {
+        embed = embed.fields(fields);
+    }
+
+    let builder = CreateMessage::new().embed(embed);
+    if let Err(why) = parent_channel.send_message(&ctx.http, builder).await {
+        error!("Error sending message: {:?}", why);
+    }
+
+    archive_thread(ctx, thread).await;
+}
+
+// スレッドをアーカイブしてロックする
+pub async fn archive_thread(ctx: &Context, thread: ChannelId) {
+    let builder = EditThread::new().archived(true).locked(true);
+    if let Err(why) = thread.edit_thread(&ctx.http, builder).await {
+        error!("スレッドのアーカイブに失敗しました: {}", why);
+    }
+}
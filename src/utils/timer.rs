@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use chrono::Utc;
+
+use serenity::all::ChannelId;
+use serenity::prelude::*;
+
+use crate::handlers::{Bot, BotKey};
+use crate::models::Session;
+use crate::utils::finish_game::{finish_game, Finish, Reply};
+
+// チャンネルの設定に制限時間がある場合、時間切れでギブアップさせるタイマーを開始する
+pub async fn start_timer(ctx: &Context, bot: &Bot, channel_id: ChannelId, session: &mut Session) {
+    session.deadline = None;
+    session.remaining = None;
+    let Some(minutes) = session.settings.time_limit.filter(|minutes| *minutes > 0) else {
+        return;
+    };
+    spawn_timer(ctx, bot, channel_id, session, minutes as i64 * 60).await;
+}
+
+// 一時停止した時点の残り時間を記録し、動いているタイマーを止める
+pub fn pause_timer(session: &mut Session) {
+    session.remaining = session
+        .deadline
+        .take()
+        .map(|deadline| (deadline - Utc::now().timestamp()).max(0));
+}
+
+// 一時停止した時点の残り時間でタイマーを再開する
+pub async fn resume_timer(ctx: &Context, bot: &Bot, channel_id: ChannelId, session: &mut Session) {
+    if let Some(remaining) = session.remaining.take() {
+        spawn_timer(ctx, bot, channel_id, session, remaining).await;
+    }
+}
+
+// 時間切れの時点で別の問題に進んでいる場合や、一時停止・再開で制限時間が変わった場合は何もしない
+async fn spawn_timer(
+    ctx: &Context,
+    bot: &Bot,
+    channel_id: ChannelId,
+    session: &mut Session,
+    seconds: i64,
+) {
+    let deadline = Utc::now().timestamp() + seconds;
+    session.deadline = Some(deadline);
+    let round = session.round;
+    let Some(session) = bot.find_session(channel_id).await else {
+        return;
+    };
+    let ctx = ctx.clone();
+
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(seconds as u64)).await;
+        let Some(bot) = ctx.data.read().await.get::<BotKey>().cloned() else {
+            return;
+        };
+
+        let mut session = session.lock().await;
+        if session.round != round
+            || session.deadline != Some(deadline)
+            || session.game.time_up().is_err()
+        {
+            return;
+        }
+
+        // 結果を表示できなかった場合は制限時間なしでゲームを続ける
+        finish_game(
+            &ctx,
+            &bot,
+            channel_id,
+            &mut session,
+            Finish::TimeUp,
+            Reply::Channel,
+        )
+        .await;
+    });
+}
@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use fluent::fluent_args;
+use serenity::all::ChannelId;
+use serenity::prelude::*;
+use tracing::error;
+
+use crate::handlers::Bot;
+use crate::i18n::t_args;
+use crate::models::{DisplayMode, Session};
+use crate::utils::qa_board::update_board;
+
+// 手番ごとの制限時間。過ぎると次のユーザーの番になる
+const TURN_TIMEOUT: Duration = Duration::from_secs(120);
+
+// 手番制の場合、誰の番かを知らせる文
+pub fn turn_notice(session: &Session) -> Option<String> {
+    session.current_turn().map(|user| {
+        t_args(
+            session.game.locale,
+            "turn-notice",
+            &fluent_args!["user" => user.mention().to_string()],
+        )
+    })
+}
+
+// 手番制の場合、最初の手番を知らせて制限時間のタイマーを開始する
+pub async fn start_turns(ctx: &Context, bot: &Bot, channel_id: ChannelId, session: &Session) {
+    let Some(notice) = turn_notice(session) else {
+        return;
+    };
+    if let Err(why) = channel_id.say(&ctx.http, notice).await {
+        error!("Error sending message: {:?}", why);
+    }
+    start_turn_timer(ctx, bot, channel_id, session).await;
+}
+
+// 質問した後に次のユーザーの番にする
+pub async fn advance_turn(ctx: &Context, bot: &Bot, channel_id: ChannelId, session: &mut Session) {
+    if session.current_turn().is_none() {
+        return;
+    }
+    session.advance_turn();
+    start_turn_timer(ctx, bot, channel_id, session).await;
+}
+
+// 制限時間内に質問しなかった場合は次のユーザーの番にする
+// 質問して手番が進んだ場合や、別の問題に進んだ場合は何もしない
+async fn start_turn_timer(ctx: &Context, bot: &Bot, channel_id: ChannelId, session: &Session) {
+    let mut serial = session.turn_serial;
+    let Some(session) = bot.find_session(channel_id).await else {
+        return;
+    };
+    let ctx = ctx.clone();
+
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(TURN_TIMEOUT).await;
+
+            let mut session = session.lock().await;
+            if session.turn_serial != serial || !session.game.is_playing() {
+                return;
+            }
+            let Some(skipped) = session.current_turn() else {
+                return;
+            };
+            session.advance_turn();
+            serial = session.turn_serial;
+
+            let next = session.current_turn().unwrap_or(skipped);
+            let content = t_args(
+                session.game.locale,
+                "turn-timeout",
+                &fluent_args![
+                    "user" => skipped.mention().to_string(),
+                    "next" => next.mention().to_string()
+                ],
+            );
+            if let Err(why) = channel_id.say(&ctx.http, content).await {
+                error!("Error sending message: {:?}", why);
+            }
+            if session.display == DisplayMode::Compact {
+                update_board(&ctx, channel_id, &mut session).await;
+            }
+        }
+    });
+}